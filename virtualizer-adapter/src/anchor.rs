@@ -67,3 +67,16 @@ pub fn apply_anchor<K: VirtualizerKey>(
     v.set_scroll_offset_clamped(target);
     true
 }
+
+/// Same as [`apply_anchor`], but resolves the key with the virtualizer's own key → index map.
+///
+/// Requires `VirtualizerOptions::maintain_key_index`; returns `false` otherwise.
+pub fn apply_anchor_with_key_index<K: VirtualizerKey>(
+    v: &mut virtualizer::Virtualizer<K>,
+    anchor: &ScrollAnchor<K>,
+) -> bool {
+    let Some(index) = v.index_for_key(&anchor.key) else {
+        return false;
+    };
+    apply_anchor(v, anchor, |_| Some(index))
+}
@@ -0,0 +1,72 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A millisecond time source for adapter-driven timing (tweens, `is_scrolling` debouncing).
+///
+/// The explicit `now_ms` APIs remain available everywhere; a time source only removes the
+/// plumbing from your frame loop (see `Controller::with_time_source`).
+pub trait TimeSource {
+    /// Returns the current time in milliseconds (monotonic, arbitrary origin).
+    fn now_ms(&self) -> u64;
+}
+
+/// A manually advanced clock, useful for deterministic tests.
+///
+/// Clones share the same underlying time, so you can keep a handle while the controller owns
+/// another.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    now_ms: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            now_ms: Arc::new(AtomicU64::new(now_ms)),
+        }
+    }
+
+    pub fn set(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, delta_ms: u64) {
+        self.now_ms.fetch_add(delta_ms, Ordering::Relaxed);
+    }
+}
+
+impl TimeSource for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::Relaxed)
+    }
+}
+
+/// A wall-clock time source based on `std::time::Instant` (milliseconds since creation).
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl TimeSource for SystemClock {
+    fn now_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
+    }
+}
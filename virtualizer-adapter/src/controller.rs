@@ -1,5 +1,6 @@
 use crate::{
-    Easing, ScrollAnchor, Tween, VirtualizerKey, apply_anchor, capture_first_visible_anchor,
+    Easing, ScrollAnchor, TimeSource, Tween, VirtualizerKey, apply_anchor,
+    apply_anchor_with_key_index, capture_first_visible_anchor,
 };
 
 /// A framework-neutral controller that wraps a `virtualizer::Virtualizer` and provides common
@@ -17,22 +18,83 @@ use crate::{
 /// - On UI scroll events: call `on_scroll(offset, now_ms)` (cancels any active tween).
 /// - On UI resize/layout: call `on_viewport_size(main)`.
 /// - Each frame/timer: call `tick(now_ms)`; if it returns `Some(offset)`, apply it to the real UI.
+///
+/// With a [`TimeSource`] attached (`with_time_source`), the `*_now` variants read the time
+/// internally (e.g. `tick_now()`), which also makes long animations easy to test with a
+/// [`crate::ManualClock`].
 #[derive(Clone, Debug)]
-pub struct Controller<K> {
+pub struct Controller<K, C = ()> {
     v: virtualizer::Virtualizer<K>,
     tween: Option<Tween>,
+    paused_at_ms: Option<u64>,
+    paused_total_ms: u64,
+    follow_tail: bool,
+    scroll_reconcile_tolerance: u64,
+    fling_velocity_threshold: f32,
+    fling_deceleration: f32,
+    snap_duration_ms: u64,
+    clock: C,
 }
 
 impl<K: VirtualizerKey> Controller<K> {
     pub fn new(options: virtualizer::VirtualizerOptions<K>) -> Self {
+        Self::from_virtualizer(virtualizer::Virtualizer::new(options))
+    }
+
+    pub fn from_virtualizer(v: virtualizer::Virtualizer<K>) -> Self {
         Self {
-            v: virtualizer::Virtualizer::new(options),
+            v,
             tween: None,
+            paused_at_ms: None,
+            paused_total_ms: 0,
+            follow_tail: false,
+            scroll_reconcile_tolerance: 0,
+            fling_velocity_threshold: 0.3,
+            fling_deceleration: 0.002,
+            snap_duration_ms: 150,
+            clock: (),
         }
     }
+}
 
-    pub fn from_virtualizer(v: virtualizer::Virtualizer<K>) -> Self {
-        Self { v, tween: None }
+impl<K: VirtualizerKey, C: TimeSource> Controller<K, C> {
+    /// Returns the current time from the attached time source.
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// Same as [`Self::tick`], using the attached time source.
+    pub fn tick_now(&mut self) -> Option<u64> {
+        let now_ms = self.clock.now_ms();
+        self.tick(now_ms)
+    }
+
+    /// Same as [`Self::on_scroll`], using the attached time source.
+    pub fn on_scroll_now(&mut self, scroll_offset: u64) {
+        let now_ms = self.clock.now_ms();
+        self.on_scroll(scroll_offset, now_ms);
+    }
+}
+
+impl<K: VirtualizerKey, C> Controller<K, C> {
+    /// Attaches a time source, enabling the `*_now` convenience methods.
+    pub fn with_time_source<T: TimeSource>(self, clock: T) -> Controller<K, T> {
+        Controller {
+            v: self.v,
+            tween: self.tween,
+            paused_at_ms: self.paused_at_ms,
+            paused_total_ms: self.paused_total_ms,
+            follow_tail: self.follow_tail,
+            scroll_reconcile_tolerance: self.scroll_reconcile_tolerance,
+            fling_velocity_threshold: self.fling_velocity_threshold,
+            fling_deceleration: self.fling_deceleration,
+            snap_duration_ms: self.snap_duration_ms,
+            clock,
+        }
+    }
+
+    pub fn time_source(&self) -> &C {
+        &self.clock
     }
 
     pub fn virtualizer(&self) -> &virtualizer::Virtualizer<K> {
@@ -51,8 +113,84 @@ impl<K: VirtualizerKey> Controller<K> {
         self.tween.is_some()
     }
 
+    /// Returns the offset the active tween is heading to, or `None` when not animating.
+    pub fn animation_target(&self) -> Option<u64> {
+        self.tween.map(|t| t.to)
+    }
+
+    /// Returns the linear time progress of the active tween in `[0, 1]` (before easing), or
+    /// `None` when not animating.
+    ///
+    /// While paused, progress is frozen at the pause time.
+    pub fn animation_progress(&self, now_ms: u64) -> Option<f32> {
+        let tween = self.tween?;
+        let now_ms = self.paused_at_ms.unwrap_or(now_ms);
+        let elapsed = now_ms.saturating_sub(tween.start_ms);
+        Some((elapsed as f32 / tween.duration_ms as f32).clamp(0.0, 1.0))
+    }
+
     pub fn cancel_animation(&mut self) {
         self.tween = None;
+        self.paused_at_ms = None;
+        self.paused_total_ms = 0;
+    }
+
+    /// Pauses the active tween at `now_ms` (e.g. while the user hovers an auto-scrolling list).
+    ///
+    /// While paused, `tick` leaves the scroll offset untouched but still debounces
+    /// `is_scrolling`. No-op if there is no active tween or it is already paused.
+    pub fn pause_animation(&mut self, now_ms: u64) {
+        if self.tween.is_some() && self.paused_at_ms.is_none() {
+            self.paused_at_ms = Some(now_ms);
+        }
+    }
+
+    /// Resumes a paused tween at `now_ms`, continuing from where it left off.
+    ///
+    /// The tween start is shifted by the paused interval, so the total animation duration is
+    /// extended accordingly.
+    pub fn resume_animation(&mut self, now_ms: u64) {
+        let Some(paused_at) = self.paused_at_ms.take() else {
+            return;
+        };
+        let paused_ms = now_ms.saturating_sub(paused_at);
+        self.paused_total_ms = self.paused_total_ms.saturating_add(paused_ms);
+        if let Some(tween) = &mut self.tween {
+            tween.start_ms = tween.start_ms.saturating_add(paused_ms);
+        }
+    }
+
+    pub fn is_animation_paused(&self) -> bool {
+        self.paused_at_ms.is_some()
+    }
+
+    /// Total time the current tween has spent paused (excluding an in-progress pause).
+    pub fn animation_paused_ms(&self) -> u64 {
+        self.paused_total_ms
+    }
+
+    /// Enables "stick to bottom" behavior for [`Self::on_count_changed`] (e.g. live logs).
+    pub fn set_follow_tail(&mut self, follow_tail: bool) {
+        self.follow_tail = follow_tail;
+    }
+
+    pub fn follow_tail(&self) -> bool {
+        self.follow_tail
+    }
+
+    /// Updates the item count (e.g. after appending).
+    ///
+    /// If `follow_tail` is enabled and the view was at the end before the change, scrolls to the
+    /// last item with `Align::End` (cancelling any active tween) and returns the applied offset.
+    /// Otherwise the scroll offset is left untouched and `None` is returned.
+    pub fn on_count_changed(&mut self, new_count: usize, now_ms: u64) -> Option<u64> {
+        let was_at_end = self.v.scroll_offset() >= self.v.max_scroll_offset();
+        self.v.set_count(new_count);
+        if !self.follow_tail || !was_at_end || new_count == 0 {
+            return None;
+        }
+        self.cancel_animation();
+        Some(self.scroll_to_index(new_count - 1, virtualizer::Align::End, now_ms))
     }
 
     pub fn on_viewport_size(&mut self, viewport_main: u32) {
@@ -61,18 +199,78 @@ impl<K: VirtualizerKey> Controller<K> {
 
     /// Call this when the UI reports a scroll offset change (e.g. user wheel/drag).
     ///
-    /// This cancels any active tween.
+    /// This cancels any active tween, unless the report is within
+    /// [`Self::scroll_reconcile_tolerance`] of the animated offset (the last applied offset or the
+    /// tween sample at `now_ms`). Such reports are the container echoing the tween's own scroll
+    /// and are ignored.
     pub fn on_scroll(&mut self, scroll_offset: u64, now_ms: u64) {
+        if let Some(tween) = self.tween {
+            let deviation = scroll_offset
+                .abs_diff(self.v.scroll_offset())
+                .min(scroll_offset.abs_diff(tween.sample(now_ms)));
+            if deviation <= self.scroll_reconcile_tolerance {
+                return;
+            }
+        }
         self.cancel_animation();
         self.v.apply_scroll_offset_event(scroll_offset, now_ms);
     }
 
+    /// Reconciles the virtualizer with the container's actual offset (e.g. after the container
+    /// clamped differently during a layout change) without treating it as user input.
+    ///
+    /// Unlike [`Self::on_scroll`], this never cancels an active tween and leaves `is_scrolling`
+    /// untouched. Returns the applied (clamped) offset.
+    pub fn sync_external_scroll(&mut self, offset: u64) -> u64 {
+        self.v.set_scroll_offset_clamped(offset);
+        self.v.scroll_offset()
+    }
+
+    /// Sets how far (in pixels) a reported scroll may deviate from the animated offset and still
+    /// be treated as self-induced by [`Self::on_scroll`] (default: 0, i.e. exact echoes only).
+    pub fn set_scroll_reconcile_tolerance(&mut self, tolerance: u64) {
+        self.scroll_reconcile_tolerance = tolerance;
+    }
+
+    pub fn scroll_reconcile_tolerance(&self) -> u64 {
+        self.scroll_reconcile_tolerance
+    }
+
+    /// Sets the release speed (px/ms) above which [`Self::on_drag_end`] flings (default: 0.3).
+    pub fn set_fling_velocity_threshold(&mut self, px_per_ms: f32) {
+        self.fling_velocity_threshold = px_per_ms.max(0.0);
+    }
+
+    pub fn fling_velocity_threshold(&self) -> f32 {
+        self.fling_velocity_threshold
+    }
+
+    /// Sets the constant fling deceleration in px/ms² (default: 0.002; clamped to a small
+    /// positive minimum).
+    pub fn set_fling_deceleration(&mut self, px_per_ms2: f32) {
+        self.fling_deceleration = px_per_ms2.max(f32::EPSILON);
+    }
+
+    pub fn fling_deceleration(&self) -> f32 {
+        self.fling_deceleration
+    }
+
+    /// Sets the duration of the settle tween started by [`Self::on_drag_end`] (default: 150ms).
+    pub fn set_snap_duration_ms(&mut self, duration_ms: u64) {
+        self.snap_duration_ms = duration_ms;
+    }
+
+    pub fn snap_duration_ms(&self) -> u64 {
+        self.snap_duration_ms
+    }
+
     /// Advances the controller.
     ///
     /// - If a tween is active, updates `scroll_offset` and returns the new offset.
-    /// - Otherwise, runs `is_scrolling` debouncing and returns `None`.
+    /// - Otherwise (or while the tween is paused), runs `is_scrolling` debouncing and returns
+    ///   `None`.
     pub fn tick(&mut self, now_ms: u64) -> Option<u64> {
-        let Some(tween) = self.tween else {
+        let Some(tween) = self.tween.filter(|_| self.paused_at_ms.is_none()) else {
             self.v.update_scrolling(now_ms);
             return None;
         };
@@ -81,7 +279,7 @@ impl<K: VirtualizerKey> Controller<K> {
         self.v.apply_scroll_offset_event_clamped(off, now_ms);
 
         if tween.is_done(now_ms) {
-            self.tween = None;
+            self.cancel_animation();
             self.v.set_is_scrolling(false);
         }
 
@@ -105,6 +303,82 @@ impl<K: VirtualizerKey> Controller<K> {
         self.v.scroll_offset()
     }
 
+    /// Scrolls to an index, animating only short distances.
+    ///
+    /// If the target is more than `max_animate_distance_px` away from the current offset, this
+    /// jumps immediately (cancelling any active tween); otherwise it starts a tween.
+    ///
+    /// Returns the clamped target offset and whether a tween was started.
+    pub fn scroll_to_index_smart(
+        &mut self,
+        index: usize,
+        align: virtualizer::Align,
+        now_ms: u64,
+        max_animate_distance_px: u64,
+        duration_ms: u64,
+        easing: Easing,
+    ) -> (u64, bool) {
+        let to = self
+            .v
+            .clamp_scroll_offset(self.v.scroll_to_index_offset(index, align));
+        if to.abs_diff(self.v.scroll_offset()) > max_animate_distance_px {
+            self.cancel_animation();
+            return (self.scroll_to_offset(to, now_ms), false);
+        }
+        (
+            self.start_tween_to_offset(to, now_ms, duration_ms, easing),
+            true,
+        )
+    }
+
+    /// Hands a released drag over to momentum scrolling.
+    ///
+    /// `velocity_px_per_ms` is the rate of change of the scroll offset at release (positive
+    /// towards the end of the list). If its magnitude exceeds
+    /// [`Self::fling_velocity_threshold`], this starts a decelerating fling tween covering
+    /// `v² / (2 * deceleration)` pixels in `|v| / deceleration` ms. Otherwise it settles on the
+    /// nearest item boundary with a [`Self::snap_duration_ms`] tween.
+    ///
+    /// Returns the clamped target offset and whether a fling was started.
+    pub fn on_drag_end(&mut self, velocity_px_per_ms: f32, now_ms: u64) -> (u64, bool) {
+        let speed = velocity_px_per_ms.abs();
+        if speed.is_finite() && speed > self.fling_velocity_threshold {
+            let decel = self.fling_deceleration;
+            let distance = (speed * speed / (2.0 * decel) + 0.5) as u64;
+            let duration_ms = (speed / decel + 0.5) as u64;
+            let from = self.v.scroll_offset();
+            let to = if velocity_px_per_ms > 0.0 {
+                from.saturating_add(distance)
+            } else {
+                from.saturating_sub(distance)
+            };
+            let to = self.start_tween_to_offset(to, now_ms, duration_ms, Easing::EaseOutQuad);
+            return (to, true);
+        }
+
+        let off = self.v.scroll_offset();
+        let to = match self.v.index_at_offset(off) {
+            Some(i) => {
+                let start = self.v.item_start(i).unwrap_or(off);
+                // Snap to the next item's start (past any gap); the last item snaps to its end.
+                let next = self.v.item_start(i + 1).unwrap_or_else(|| {
+                    start.saturating_add(self.v.item_size(i).unwrap_or(0) as u64)
+                });
+                if off.saturating_sub(start) <= next.saturating_sub(off) {
+                    start
+                } else {
+                    next
+                }
+            }
+            None => off,
+        };
+        let duration_ms = self.snap_duration_ms;
+        (
+            self.start_tween_to_offset(to, now_ms, duration_ms, Easing::SmoothStep),
+            false,
+        )
+    }
+
     /// Starts a tween to an index (adapter-driven).
     ///
     /// Returns the clamped target offset.
@@ -120,6 +394,23 @@ impl<K: VirtualizerKey> Controller<K> {
         self.start_tween_to_offset(to, now_ms, duration_ms, easing)
     }
 
+    /// Starts a tween that reveals the span `start..=end` (see
+    /// `Virtualizer::scroll_to_range_offset`).
+    ///
+    /// Returns the clamped target offset.
+    pub fn start_tween_to_range(
+        &mut self,
+        start: usize,
+        end: usize,
+        align: virtualizer::Align,
+        now_ms: u64,
+        duration_ms: u64,
+        easing: Easing,
+    ) -> u64 {
+        let to = self.v.scroll_to_range_offset(start, end, align);
+        self.start_tween_to_offset(to, now_ms, duration_ms, easing)
+    }
+
     /// Starts a tween to an offset (adapter-driven).
     ///
     /// Returns the clamped target offset.
@@ -132,6 +423,7 @@ impl<K: VirtualizerKey> Controller<K> {
     ) -> u64 {
         let to = self.v.clamp_scroll_offset(offset);
         let from = self.v.scroll_offset();
+        self.cancel_animation();
         self.tween = Some(Tween::new(from, to, now_ms, duration_ms, easing));
         to
     }
@@ -167,4 +459,11 @@ impl<K: VirtualizerKey> Controller<K> {
         self.cancel_animation();
         apply_anchor(&mut self.v, anchor, key_to_index)
     }
+
+    /// Same as [`Self::apply_anchor`], but uses the virtualizer's key → index map (requires
+    /// `VirtualizerOptions::maintain_key_index`).
+    pub fn apply_anchor_with_key_index(&mut self, anchor: &ScrollAnchor<K>) -> bool {
+        self.cancel_animation();
+        apply_anchor_with_key_index(&mut self.v, anchor)
+    }
 }
@@ -5,6 +5,7 @@
 //!
 //! - Scroll anchoring (e.g. prepend in chat/timelines without visual jumps)
 //! - Tween-based smooth scrolling helpers (optional; adapter-driven)
+//! - Pluggable time sources (`TimeSource`) for controller-driven timing
 //!
 //! This crate is intentionally framework-agnostic (no ratatui/egui bindings).
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -16,6 +17,7 @@ extern crate alloc;
 extern crate std;
 
 mod anchor;
+mod clock;
 mod controller;
 mod key;
 mod tween;
@@ -23,7 +25,12 @@ mod tween;
 #[cfg(test)]
 mod tests;
 
-pub use anchor::{ScrollAnchor, apply_anchor, capture_first_visible_anchor};
+pub use anchor::{
+    ScrollAnchor, apply_anchor, apply_anchor_with_key_index, capture_first_visible_anchor,
+};
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{ManualClock, TimeSource};
 pub use controller::Controller;
 pub use key::VirtualizerKey;
 pub use tween::{Easing, Tween};
@@ -67,3 +67,263 @@ fn tween_retarget_is_continuous() {
     assert_eq!(t.to, 200);
     assert_eq!(t.start_ms, 50);
 }
+
+#[test]
+fn controller_time_source_drives_tick_now() {
+    let clock = ManualClock::new(0);
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1000, |_| 1))
+        .with_time_source(clock.clone());
+    c.virtualizer_mut().set_viewport_size(10);
+
+    let to = c.start_tween_to_index(
+        500,
+        virtualizer::Align::Start,
+        c.now_ms(),
+        100,
+        Easing::Linear,
+    );
+
+    clock.advance(50);
+    let mid = c.tick_now().unwrap();
+    assert!(mid > 0 && mid < to);
+
+    clock.set(100);
+    assert_eq!(c.tick_now(), Some(to));
+    assert!(!c.is_animating());
+
+    // Debouncing also uses the attached clock.
+    c.on_scroll_now(10);
+    assert!(c.virtualizer().is_scrolling());
+    clock.advance(1000);
+    assert_eq!(c.tick_now(), None);
+    assert!(!c.virtualizer().is_scrolling());
+}
+
+#[test]
+fn controller_pause_extends_tween_by_paused_interval() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1000, |_| 1));
+    c.virtualizer_mut().set_viewport_size(10);
+
+    let to = c.start_tween_to_offset(100, 0, 100, Easing::Linear);
+    assert_eq!(c.tick(40), Some(40));
+
+    c.pause_animation(40);
+    assert!(c.is_animation_paused());
+    assert_eq!(c.tick(60), None);
+    assert_eq!(c.tick(90), None);
+    assert_eq!(c.virtualizer().scroll_offset(), 40);
+    assert!(c.is_animating());
+
+    c.resume_animation(90);
+    assert_eq!(c.animation_paused_ms(), 50);
+    assert_eq!(c.tick(100), Some(50));
+
+    // Original end was t=100; the 50ms pause pushes it to t=150.
+    assert_eq!(c.tick(149), Some(99));
+    assert!(c.is_animating());
+    assert_eq!(c.tick(150), Some(to));
+    assert!(!c.is_animating());
+}
+
+#[test]
+fn controller_follow_tail_pins_only_when_at_end() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(20, |_| 1));
+    c.set_follow_tail(true);
+    c.on_viewport_size(10);
+    c.scroll_to_offset(10, 0);
+
+    // Pinned: appends keep the view at the bottom.
+    assert_eq!(c.on_count_changed(25, 16), Some(15));
+    assert_eq!(c.virtualizer().scroll_offset(), 15);
+
+    // Scrolled up: appends don't move the view.
+    c.on_scroll(5, 32);
+    assert_eq!(c.on_count_changed(30, 48), None);
+    assert_eq!(c.virtualizer().scroll_offset(), 5);
+    assert_eq!(c.virtualizer().options().count, 30);
+
+    // Disabled: no auto-scroll even at the end.
+    c.scroll_to_offset(20, 64);
+    c.set_follow_tail(false);
+    assert_eq!(c.on_count_changed(40, 80), None);
+    assert_eq!(c.virtualizer().scroll_offset(), 20);
+}
+
+#[test]
+fn controller_exposes_animation_target_and_progress() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1000, |_| 1));
+    c.virtualizer_mut().set_viewport_size(10);
+    assert_eq!(c.animation_target(), None);
+    assert_eq!(c.animation_progress(0), None);
+
+    let to = c.start_tween_to_offset(200, 100, 100, Easing::Linear);
+    assert_eq!(c.animation_target(), Some(to));
+
+    let mut prev = -1.0f32;
+    for now in (100..=200).step_by(10) {
+        let p = c.animation_progress(now).unwrap();
+        assert!(p > prev);
+        prev = p;
+        c.tick(now);
+    }
+    assert_eq!(prev, 1.0);
+    assert_eq!(c.animation_target(), None);
+    assert_eq!(c.animation_progress(210), None);
+}
+
+#[test]
+fn controller_on_scroll_ignores_echoes_of_the_tween() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1000, |_| 1));
+    c.virtualizer_mut().set_viewport_size(10);
+    c.start_tween_to_offset(100, 0, 100, Easing::Linear);
+    assert_eq!(c.tick(40), Some(40));
+
+    // The container echoes the offset we just applied: keep animating.
+    c.on_scroll(40, 45);
+    assert!(c.is_animating());
+    assert_eq!(c.tick(50), Some(50));
+
+    // Within the tolerance of the current sample: still self-induced.
+    c.set_scroll_reconcile_tolerance(2);
+    c.on_scroll(57, 55);
+    assert!(c.is_animating());
+    assert_eq!(c.virtualizer().scroll_offset(), 50);
+
+    // A larger deviation is a user scroll and cancels the tween.
+    c.on_scroll(20, 60);
+    assert!(!c.is_animating());
+    assert_eq!(c.virtualizer().scroll_offset(), 20);
+}
+
+#[test]
+fn controller_scroll_to_index_smart_jumps_far_and_tweens_near() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1000, |_| 10));
+    c.virtualizer_mut().set_viewport_size(100);
+
+    // Exactly at the threshold: animate.
+    let (to, animated) =
+        c.scroll_to_index_smart(50, virtualizer::Align::Start, 0, 500, 100, Easing::Linear);
+    assert_eq!((to, animated), (500, true));
+    assert!(c.is_animating());
+    assert_eq!(c.virtualizer().scroll_offset(), 0);
+    assert_eq!(c.tick(100), Some(500));
+
+    // One pixel past the threshold: jump.
+    let (to, animated) = c.scroll_to_index_smart(
+        100,
+        virtualizer::Align::Start,
+        200,
+        499,
+        100,
+        Easing::Linear,
+    );
+    assert_eq!((to, animated), (1000, false));
+    assert!(!c.is_animating());
+    assert_eq!(c.virtualizer().scroll_offset(), 1000);
+
+    // A jump cancels an in-flight tween.
+    c.scroll_to_index_smart(
+        110,
+        virtualizer::Align::Start,
+        300,
+        1_000,
+        100,
+        Easing::Linear,
+    );
+    assert!(c.is_animating());
+    let (to, animated) = c.scroll_to_index_smart(
+        900,
+        virtualizer::Align::Start,
+        310,
+        1_000,
+        100,
+        Easing::Linear,
+    );
+    assert_eq!((to, animated), (9000, false));
+    assert!(!c.is_animating());
+}
+
+#[test]
+fn drag_end_flings_fast_releases_and_snaps_slow_ones() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1_000, |_| 10));
+    c.on_viewport_size(100);
+    c.on_scroll(1_004, 0);
+
+    // 1px/ms at 0.002px/ms²: 250px over 500ms.
+    let (to, flung) = c.on_drag_end(1.0, 10);
+    assert_eq!((to, flung), (1_254, true));
+    assert!(c.is_animating());
+    let mid = c.tick(260).unwrap();
+    assert!(
+        mid > 1_129 && mid < 1_254,
+        "ease-out should front-load motion: {mid}"
+    );
+    assert_eq!(c.tick(510), Some(1_254));
+    assert!(!c.is_animating());
+
+    // Negative velocity flings backwards and is clamped at the start.
+    let (to, flung) = c.on_drag_end(-10.0, 600);
+    assert_eq!((to, flung), (0, true));
+
+    // A near-zero release settles on the nearest item boundary.
+    c.on_scroll(1_004, 700);
+    let (to, flung) = c.on_drag_end(0.01, 700);
+    assert_eq!((to, flung), (1_000, false));
+    assert_eq!(c.tick(700 + c.snap_duration_ms()), Some(1_000));
+
+    c.on_scroll(1_006, 1_000);
+    assert_eq!(c.on_drag_end(-0.01, 1_000), (1_010, false));
+
+    // Thresholds are configurable.
+    c.set_fling_velocity_threshold(5.0);
+    assert!(!c.on_drag_end(1.0, 1_100).1);
+}
+
+#[test]
+fn drag_end_snaps_past_the_gap_to_the_next_item() {
+    // Items start every 14px: 10px rows plus a 4px gap.
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(1_000, |_| 10).with_gap(4));
+    c.on_viewport_size(100);
+
+    c.on_scroll(1_408, 0);
+    assert_eq!(c.on_drag_end(0.0, 0), (1_414, false));
+
+    // Releasing inside the gap also lands on the next item, not the gap.
+    c.on_scroll(1_412, 1_000);
+    assert_eq!(c.on_drag_end(0.0, 1_000), (1_414, false));
+
+    c.on_scroll(1_403, 2_000);
+    assert_eq!(c.on_drag_end(0.0, 2_000), (1_400, false));
+}
+
+#[test]
+fn ease_out_quad_decelerates() {
+    assert_eq!(Easing::EaseOutQuad.sample(0.0), 0.0);
+    assert_eq!(Easing::EaseOutQuad.sample(0.5), 0.75);
+    assert_eq!(Easing::EaseOutQuad.sample(1.0), 1.0);
+}
+
+#[test]
+fn sync_external_scroll_keeps_the_tween_running() {
+    let mut c = Controller::new(virtualizer::VirtualizerOptions::new(100, |_| 10));
+    c.on_viewport_size(100);
+    c.start_tween_to_offset(400, 0, 100, Easing::Linear);
+    assert_eq!(c.tick(50), Some(200));
+
+    assert!(c.virtualizer().is_scrolling());
+
+    assert_eq!(c.sync_external_scroll(190), 190);
+    assert!(c.is_animating());
+    assert!(c.virtualizer().is_scrolling());
+    assert_eq!(c.virtualizer().scroll_offset(), 190);
+    assert_eq!(c.sync_external_scroll(u64::MAX), 900);
+    assert!(c.is_animating());
+
+    assert_eq!(c.tick(100), Some(400));
+    assert!(!c.is_animating());
+
+    // `on_scroll` with the same offset would have cancelled it.
+    c.start_tween_to_offset(0, 200, 100, Easing::Linear);
+    c.on_scroll(350, 250);
+    assert!(!c.is_animating());
+}
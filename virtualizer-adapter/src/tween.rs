@@ -57,6 +57,8 @@ pub enum Easing {
     Linear,
     SmoothStep,
     EaseInOutCubic,
+    /// Quadratic ease-out; matches the motion of a constant deceleration (used for flings).
+    EaseOutQuad,
 }
 
 impl Easing {
@@ -73,6 +75,10 @@ impl Easing {
                     1.0 - (u * u * u) / 2.0
                 }
             }
+            Self::EaseOutQuad => {
+                let u = 1.0 - t;
+                1.0 - u * u
+            }
         }
     }
 }
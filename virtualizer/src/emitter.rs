@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::Range;
 
 /// Helper to build correct `range_extractor` implementations without allocations.
@@ -33,6 +35,16 @@ impl<'a> IndexEmitter<'a> {
         }
     }
 
+    /// Creates a [`BufferedIndexEmitter`], which accepts indexes in any order and emits them
+    /// sorted and deduplicated when finished (or dropped).
+    pub fn buffered(range: Range, emit: &'a mut dyn FnMut(usize)) -> BufferedIndexEmitter<'a> {
+        BufferedIndexEmitter {
+            inner: Self::new(range, emit),
+            indexes: SmallBuf::new(),
+            spans: SmallBuf::new(),
+        }
+    }
+
     pub fn range(&self) -> Range {
         self.range
     }
@@ -99,3 +111,145 @@ impl<'a> IndexEmitter<'a> {
         self.emit_range(start, end);
     }
 }
+
+/// An [`IndexEmitter`] that accepts indexes in any order.
+///
+/// Pushed indexes and spans are buffered and emitted in ascending order, without duplicates, by
+/// [`Self::finish`] or on drop. Out-of-bounds indexes are still ignored (and debug-asserted).
+///
+/// Up to 16 single indexes and 4 spans are stored inline; only larger workloads allocate.
+///
+/// # Example
+///
+/// ```no_run
+/// use virtualizer::{IndexEmitter, Range};
+///
+/// let pinned = [40, 3, 0];
+/// let extractor = move |range: Range, emit: &mut dyn FnMut(usize)| {
+///     let mut e = IndexEmitter::buffered(range, emit);
+///     for &i in &pinned {
+///         e.push(i);
+///     }
+///     e.push_overscanned();
+/// };
+/// ```
+pub struct BufferedIndexEmitter<'a> {
+    inner: IndexEmitter<'a>,
+    indexes: SmallBuf<usize, 16>,
+    spans: SmallBuf<(usize, usize), 4>,
+}
+
+impl BufferedIndexEmitter<'_> {
+    pub fn range(&self) -> Range {
+        self.inner.range
+    }
+
+    pub fn push(&mut self, index: usize) {
+        self.indexes.push(index);
+    }
+
+    /// Buffers `start_index..end_index` (clamped to `count` on emission).
+    pub fn push_range(&mut self, start_index: usize, end_index: usize) {
+        if start_index < end_index {
+            self.spans.push((start_index, end_index));
+        }
+    }
+
+    pub fn push_visible(&mut self) {
+        let range = self.inner.range;
+        self.push_range(range.start_index, range.end_index);
+    }
+
+    pub fn push_overscanned(&mut self) {
+        let range = self.inner.range;
+        self.push_range(
+            range.start_index.saturating_sub(range.overscan),
+            range.end_index.saturating_add(range.overscan),
+        );
+    }
+
+    /// Emits everything buffered so far (sorted, deduplicated). Equivalent to dropping `self`.
+    pub fn finish(self) {}
+
+    fn flush(&mut self) {
+        let indexes = self.indexes.as_mut_slice();
+        indexes.sort_unstable();
+        let spans = self.spans.as_mut_slice();
+        spans.sort_unstable();
+
+        let count = self.inner.range.count;
+        let inner = &mut self.inner;
+        // Everything below `next` has been emitted (or skipped as a duplicate).
+        let mut next = 0;
+        let mut emit = |i: usize, next: &mut usize| {
+            if i >= *next {
+                inner.emit(i);
+                *next = i + 1;
+            }
+        };
+        let mut indexes = indexes.iter().copied().peekable();
+        for &(start, end) in spans.iter() {
+            while let Some(i) = indexes.next_if(|&i| i < start) {
+                emit(i, &mut next);
+            }
+            let end = end.min(count);
+            for i in start.max(next)..end {
+                emit(i, &mut next);
+            }
+        }
+        for i in indexes {
+            emit(i, &mut next);
+        }
+        self.indexes.clear();
+        self.spans.clear();
+    }
+}
+
+impl Drop for BufferedIndexEmitter<'_> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// A vector that stores up to `N` items inline before spilling to the heap.
+struct SmallBuf<T, const N: usize> {
+    inline: [T; N],
+    len: usize,
+    spill: Vec<T>,
+}
+
+impl<T: Copy + Default, const N: usize> SmallBuf<T, N> {
+    fn new() -> Self {
+        Self {
+            inline: [T::default(); N],
+            len: 0,
+            spill: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        if !self.spill.is_empty() {
+            self.spill.push(value);
+        } else if self.len < N {
+            self.inline[self.len] = value;
+            self.len += 1;
+        } else {
+            self.spill.reserve(N * 2);
+            self.spill.extend_from_slice(&self.inline);
+            self.spill.push(value);
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.spill.is_empty() {
+            &mut self.inline[..self.len]
+        } else {
+            &mut self.spill
+        }
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        self.spill.clear();
+    }
+}
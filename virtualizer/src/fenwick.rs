@@ -23,27 +23,28 @@ impl Fenwick {
     }
 
     pub(crate) fn from_sizes(sizes: &[u32], gap: u32) -> Self {
+        Self::from_sizes_with_gaps(sizes, |_| gap)
+    }
+
+    /// Builds a Fenwick tree where item `i` contributes `sizes[i] + gap_after(i)`.
+    ///
+    /// `gap_after` is not called for the last item (there is no trailing gap).
+    pub(crate) fn from_sizes_with_gaps(sizes: &[u32], gap_after: impl Fn(usize) -> u32) -> Self {
         let n = sizes.len();
         let mut tree = alloc::vec![0u64; n + 1];
-        let mut total = 0u64;
         let max_bit = if n == 0 {
             0
         } else {
             highest_power_of_two_leq(n)
         };
-        let gap = gap as u64;
-        for i in 1..=n {
-            let mut v = sizes[i - 1] as u64;
-            if gap > 0 && i < n {
-                v = v.saturating_add(gap);
-            }
-            total = total.saturating_add(v);
-            tree[i] = tree[i].saturating_add(v);
-            let j = i + lsb(i);
-            if j <= n {
-                tree[j] = tree[j].saturating_add(tree[i]);
+        let total = build_in(&mut tree[1..], |i| {
+            let v = sizes[i] as u64;
+            if i + 1 < n {
+                v.saturating_add(gap_after(i) as u64)
+            } else {
+                v
             }
-        }
+        });
         Self {
             tree,
             total,
@@ -51,10 +52,45 @@ impl Fenwick {
         }
     }
 
+    /// Builds a Fenwick tree over `n` items of `size` separated by `gap`, in `O(n)` without any
+    /// per-item callbacks.
+    pub(crate) fn from_uniform(n: usize, size: u32, gap: u32) -> Self {
+        let mut fenwick = Self::new(n);
+        if n == 0 {
+            return fenwick;
+        }
+        let stride = size as u64 + gap as u64;
+        for i in 1..=n {
+            fenwick.tree[i] = stride.saturating_mul(lsb(i) as u64);
+        }
+        fenwick.total = stride.saturating_mul(n as u64);
+        // The last item has no trailing gap.
+        fenwick.add(n - 1, -(gap as i64));
+        fenwick
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.tree.len().saturating_sub(1)
     }
 
+    /// Returns how many values the tree can hold without reallocating.
+    pub(crate) fn capacity(&self) -> usize {
+        self.tree.capacity().saturating_sub(1)
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.tree.reserve(additional);
+    }
+
+    pub(crate) fn reserve_exact(&mut self, additional: usize) {
+        self.tree.reserve_exact(additional);
+    }
+
+    /// Shrinks capacity to hold at least `min_len` values (and at least the current length).
+    pub(crate) fn shrink_to(&mut self, min_len: usize) {
+        self.tree.shrink_to(min_len.saturating_add(1));
+    }
+
     pub(crate) fn truncate(&mut self, new_len: usize) {
         let cur = self.len();
         if new_len >= cur {
@@ -102,28 +138,11 @@ impl Fenwick {
         } else if delta < 0 {
             self.total = self.total.saturating_sub((-delta) as u64);
         }
-        let mut i = index + 1;
-        while i <= n {
-            let cur = self.tree[i] as i128;
-            let next = cur + delta as i128;
-            debug_assert!(
-                next >= 0,
-                "Fenwick underflow (idx={i}, cur={cur}, delta={delta})"
-            );
-            self.tree[i] = next.clamp(0, u64::MAX as i128) as u64;
-            i += lsb(i);
-        }
+        add_in(&mut self.tree[1..], index, delta);
     }
 
     pub(crate) fn prefix_sum(&self, count: usize) -> u64 {
-        let n = self.len();
-        let mut i = cmp::min(count, n);
-        let mut sum = 0u64;
-        while i > 0 {
-            sum = sum.saturating_add(self.tree[i]);
-            i &= i - 1;
-        }
-        sum
+        prefix_sum_in(&self.tree[1..], count)
     }
 
     pub(crate) fn total(&self) -> u64 {
@@ -134,31 +153,80 @@ impl Fenwick {
     ///
     /// This is useful to map an offset to an index:
     /// - `index = lower_bound(offset)` returns the item index at `offset` (clamped).
-    pub(crate) fn lower_bound(&self, mut target: u64) -> usize {
-        let n = self.len();
-        if n == 0 {
-            return 0;
+    pub(crate) fn lower_bound(&self, target: u64) -> usize {
+        lower_bound_in(&self.tree[1..], self.max_bit, target)
+    }
+}
+
+// Slice-based Fenwick primitives.
+//
+// `nodes[i - 1]` stores the 1-indexed Fenwick node `i`, so a tree over `n` values fits in a slice
+// of length `n`. These never allocate and back both `Fenwick` and fixed-capacity storage.
+
+/// Initializes `nodes` from per-index values and returns their total.
+pub(crate) fn build_in(nodes: &mut [u64], value: impl Fn(usize) -> u64) -> u64 {
+    let n = nodes.len();
+    let mut total = 0u64;
+    for i in 1..=n {
+        let v = value(i - 1);
+        total = total.saturating_add(v);
+        nodes[i - 1] = nodes[i - 1].saturating_add(v);
+        let j = i + lsb(i);
+        if j <= n {
+            nodes[j - 1] = nodes[j - 1].saturating_add(nodes[i - 1]);
         }
+    }
+    total
+}
 
-        let mut idx = 0usize;
-        let mut bit = self.max_bit;
-        while bit != 0 {
-            let next = idx + bit;
-            if next <= n && self.tree[next] <= target {
-                target -= self.tree[next];
-                idx = next;
-            }
-            bit >>= 1;
+pub(crate) fn add_in(nodes: &mut [u64], index: usize, delta: i64) {
+    let n = nodes.len();
+    let mut i = index + 1;
+    while i <= n {
+        let cur = nodes[i - 1] as i128;
+        let next = cur + delta as i128;
+        debug_assert!(
+            next >= 0,
+            "Fenwick underflow (idx={i}, cur={cur}, delta={delta})"
+        );
+        nodes[i - 1] = next.clamp(0, u64::MAX as i128) as u64;
+        i += lsb(i);
+    }
+}
+
+pub(crate) fn prefix_sum_in(nodes: &[u64], count: usize) -> u64 {
+    let mut i = cmp::min(count, nodes.len());
+    let mut sum = 0u64;
+    while i > 0 {
+        sum = sum.saturating_add(nodes[i - 1]);
+        i &= i - 1;
+    }
+    sum
+}
+
+/// Returns the number of values whose prefix sum is <= `target`.
+///
+/// `max_bit` must be `highest_power_of_two_leq(nodes.len())` (or 0 for an empty slice).
+pub(crate) fn lower_bound_in(nodes: &[u64], max_bit: usize, mut target: u64) -> usize {
+    let n = nodes.len();
+    let mut idx = 0usize;
+    let mut bit = max_bit;
+    while bit != 0 {
+        let next = idx + bit;
+        if next <= n && nodes[next - 1] <= target {
+            target -= nodes[next - 1];
+            idx = next;
         }
-        idx
+        bit >>= 1;
     }
+    idx
 }
 
 fn lsb(i: usize) -> usize {
     i & i.wrapping_neg()
 }
 
-fn highest_power_of_two_leq(n: usize) -> usize {
+pub(crate) fn highest_power_of_two_leq(n: usize) -> usize {
     let mut p = 1usize;
     while p <= n / 2 {
         p <<= 1;
@@ -0,0 +1,298 @@
+use core::cmp;
+
+use crate::fenwick::{add_in, build_in, highest_power_of_two_leq, lower_bound_in, prefix_sum_in};
+use crate::virtualizer::align_target;
+use crate::{Align, Rounding, VirtualItem, VirtualRange};
+
+/// A fixed-capacity virtualizer for targets without an allocator.
+///
+/// All state (`sizes`, measured flags and the prefix sums) lives in inline arrays of length `N`,
+/// so this type never allocates. It supports the core query APIs (`virtual_range`,
+/// `index_at_offset`, `scroll_to_index_offset`, iteration) and dynamic measurement by index.
+///
+/// Compared to [`crate::Virtualizer`], there is no key-based measurement cache, no callbacks and
+/// no `scroll_margin`/scroll padding; `count` is capped at `N`.
+#[derive(Clone, Debug)]
+pub struct VirtualizerFixed<const N: usize> {
+    count: usize,
+    gap: u32,
+    padding_start: u32,
+    padding_end: u32,
+    overscan: usize,
+    viewport_size: u32,
+    scroll_offset: u64,
+
+    sizes: [u32; N],
+    measured: [bool; N],
+    nodes: [u64; N],
+    total: u64,
+}
+
+impl<const N: usize> VirtualizerFixed<N> {
+    /// Creates a virtualizer with `count` items (capped at `N`) sized by `estimate_size`.
+    pub fn new(count: usize, estimate_size: impl Fn(usize) -> u32) -> Self {
+        let mut v = Self::empty();
+        v.count = count.min(N);
+        for i in 0..v.count {
+            v.sizes[i] = estimate_size(i);
+        }
+        v.rebuild();
+        v
+    }
+
+    /// Creates a virtualizer from exact sizes (truncated to `N`); all items are marked measured.
+    pub fn from_sizes(sizes: &[u32]) -> Self {
+        let mut v = Self::empty();
+        v.count = sizes.len().min(N);
+        v.sizes[..v.count].copy_from_slice(&sizes[..v.count]);
+        v.measured[..v.count].fill(true);
+        v.rebuild();
+        v
+    }
+
+    fn empty() -> Self {
+        Self {
+            count: 0,
+            gap: 0,
+            padding_start: 0,
+            padding_end: 0,
+            overscan: 1,
+            viewport_size: 0,
+            scroll_offset: 0,
+            sizes: [0; N],
+            measured: [false; N],
+            nodes: [0; N],
+            total: 0,
+        }
+    }
+
+    pub fn with_gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self.rebuild();
+        self
+    }
+
+    pub fn with_padding(mut self, padding_start: u32, padding_end: u32) -> Self {
+        self.padding_start = padding_start;
+        self.padding_end = padding_end;
+        self
+    }
+
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Updates the item count (capped at `N`). New items are sized by `estimate_size`.
+    pub fn set_count(&mut self, count: usize, estimate_size: impl Fn(usize) -> u32) {
+        let count = count.min(N);
+        if count == self.count {
+            return;
+        }
+        for i in self.count..count {
+            self.sizes[i] = estimate_size(i);
+            self.measured[i] = false;
+        }
+        self.count = count;
+        self.rebuild();
+    }
+
+    pub fn viewport_size(&self) -> u32 {
+        self.viewport_size
+    }
+
+    pub fn set_viewport_size(&mut self, viewport_size: u32) {
+        self.viewport_size = viewport_size;
+    }
+
+    pub fn scroll_offset(&self) -> u64 {
+        self.scroll_offset
+    }
+
+    pub fn set_scroll_offset(&mut self, scroll_offset: u64) {
+        self.scroll_offset = scroll_offset;
+    }
+
+    pub fn set_scroll_offset_clamped(&mut self, scroll_offset: u64) {
+        self.scroll_offset = scroll_offset.min(self.max_scroll_offset());
+    }
+
+    /// Updates an item size. Returns `true` if the size changed.
+    pub fn measure(&mut self, index: usize, size: u32) -> bool {
+        if index >= self.count {
+            return false;
+        }
+        self.measured[index] = true;
+        let cur = self.sizes[index];
+        if cur == size {
+            return false;
+        }
+        self.sizes[index] = size;
+        let delta = size as i64 - cur as i64;
+        self.total = (self.total as i64 + delta).max(0) as u64;
+        add_in(&mut self.nodes[..self.count], index, delta);
+        true
+    }
+
+    pub fn is_measured(&self, index: usize) -> bool {
+        index < self.count && self.measured[index]
+    }
+
+    pub fn item_size(&self, index: usize) -> Option<u32> {
+        (index < self.count).then(|| self.sizes[index])
+    }
+
+    pub fn item_start(&self, index: usize) -> Option<u64> {
+        (index < self.count).then(|| self.start_of(index))
+    }
+
+    pub fn item(&self, index: usize) -> Option<VirtualItem> {
+        (index < self.count).then(|| VirtualItem {
+            index,
+            start: self.start_of(index),
+            size: self.sizes[index],
+        })
+    }
+
+    pub fn total_size(&self) -> u64 {
+        (self.padding_start as u64)
+            .saturating_add(self.total)
+            .saturating_add(self.padding_end as u64)
+    }
+
+    pub fn max_scroll_offset(&self) -> u64 {
+        self.total_size().saturating_sub(self.viewport_size as u64)
+    }
+
+    /// Returns the item at `offset` (clamped to the last item), or `None` when empty.
+    pub fn index_at_offset(&self, offset: u64) -> Option<usize> {
+        if self.count == 0 {
+            return None;
+        }
+        let ps = self.padding_start as u64;
+        if offset < ps {
+            return Some(0);
+        }
+        let consumed = lower_bound_in(&self.nodes[..self.count], self.max_bit(), offset - ps);
+        Some(consumed.min(self.count - 1))
+    }
+
+    pub fn visible_range(&self) -> VirtualRange {
+        let empty = VirtualRange {
+            start_index: 0,
+            end_index: 0,
+        };
+        if self.count == 0 || self.viewport_size == 0 {
+            return empty;
+        }
+        let total = self.total_size();
+        let off = self.scroll_offset.min(self.max_scroll_offset());
+        if off >= total {
+            return VirtualRange {
+                start_index: self.count,
+                end_index: self.count,
+            };
+        }
+        let end_inclusive = off
+            .saturating_add(self.viewport_size as u64)
+            .saturating_sub(1)
+            .max(off);
+        let start = self.index_at_offset(off).unwrap_or(self.count);
+        let end = self
+            .index_at_offset(end_inclusive)
+            .map_or(self.count, |i| i + 1);
+        VirtualRange {
+            start_index: start.min(self.count),
+            end_index: end.min(self.count),
+        }
+    }
+
+    /// Returns the visible range expanded by `overscan`.
+    pub fn virtual_range(&self) -> VirtualRange {
+        let mut range = self.visible_range();
+        if range.is_empty() {
+            return range;
+        }
+        range.start_index = range.start_index.saturating_sub(self.overscan);
+        range.end_index = cmp::min(self.count, range.end_index.saturating_add(self.overscan));
+        range
+    }
+
+    /// Computes the (clamped) scroll offset that brings `index` into view with `align`.
+    pub fn scroll_to_index_offset(&self, index: usize, align: Align) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let index = index.min(self.count - 1);
+        let start = self.start_of(index);
+        let end = start.saturating_add(self.sizes[index] as u64);
+        align_target(
+            start,
+            end,
+            align,
+            self.scroll_offset,
+            self.viewport_size,
+            (0, 0),
+            Rounding::HalfUp,
+        )
+        .min(self.max_scroll_offset())
+    }
+
+    pub fn for_each_virtual_item(&self, mut f: impl FnMut(VirtualItem)) {
+        let range = self.virtual_range();
+        if range.is_empty() {
+            return;
+        }
+        let mut start = self.start_of(range.start_index);
+        for i in range {
+            let size = self.sizes[i];
+            f(VirtualItem {
+                index: i,
+                start,
+                size,
+            });
+            start = start
+                .saturating_add(size as u64)
+                .saturating_add(self.gap_after(i) as u64);
+        }
+    }
+
+    fn start_of(&self, index: usize) -> u64 {
+        (self.padding_start as u64).saturating_add(prefix_sum_in(&self.nodes[..self.count], index))
+    }
+
+    fn gap_after(&self, index: usize) -> u32 {
+        if index + 1 < self.count { self.gap } else { 0 }
+    }
+
+    fn max_bit(&self) -> usize {
+        if self.count == 0 {
+            0
+        } else {
+            highest_power_of_two_leq(self.count)
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let count = self.count;
+        let gap = self.gap as u64;
+        let sizes = &self.sizes;
+        self.nodes = [0; N];
+        self.total = build_in(&mut self.nodes[..count], |i| {
+            let size = sizes[i] as u64;
+            if i + 1 < count {
+                size.saturating_add(gap)
+            } else {
+                size
+            }
+        });
+    }
+}
@@ -8,6 +8,20 @@ pub(crate) type KeySizeMap<K> = HashMap<K, u32>;
 #[cfg(not(feature = "std"))]
 pub(crate) type KeySizeMap<K> = BTreeMap<K, u32>;
 
+#[cfg(feature = "std")]
+pub(crate) type KeyIndexMap<K> = HashMap<K, usize>;
+#[cfg(not(feature = "std"))]
+pub(crate) type KeyIndexMap<K> = BTreeMap<K, usize>;
+
+/// Shrinks the key cache capacity (a no-op for `BTreeMap`, which has no spare capacity).
+#[cfg(feature = "std")]
+pub(crate) fn shrink_key_sizes<K: KeyCacheKey>(map: &mut KeySizeMap<K>, min_capacity: usize) {
+    map.shrink_to(min_capacity);
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn shrink_key_sizes<K: KeyCacheKey>(_map: &mut KeySizeMap<K>, _min_capacity: usize) {}
+
 #[cfg(feature = "std")]
 #[doc(hidden)]
 pub trait KeyCacheKey: core::hash::Hash + Eq {}
@@ -19,3 +33,43 @@ impl<K: core::hash::Hash + Eq> KeyCacheKey for K {}
 pub trait KeyCacheKey: Ord {}
 #[cfg(not(feature = "std"))]
 impl<K: Ord> KeyCacheKey for K {}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes arbitrary bytes into an [`ItemKey`](crate::ItemKey) using 64-bit FNV-1a.
+///
+/// The result is deterministic across platforms and runs (no random seed), which makes it
+/// suitable for deriving stable keys from content identity (e.g. string ids) while keeping the
+/// default `u64` key cache.
+pub fn hash_key(bytes: &[u8]) -> crate::ItemKey {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// A `u64` key derived from content via [`hash_key`] (64-bit FNV-1a).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FnvKey(pub u64);
+
+impl FnvKey {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self(hash_key(bytes))
+    }
+}
+
+impl From<&str> for FnvKey {
+    fn from(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl From<FnvKey> for crate::ItemKey {
+    fn from(key: FnvKey) -> Self {
+        key.0
+    }
+}
@@ -14,8 +14,11 @@
 //! # Feature flags
 //!
 //! - `std` (default): Enables `std` support.
-//! - `serde`: Adds `serde::Serialize`/`Deserialize` for the public data types (ranges/items/state).
+//! - `serde`: Adds `serde::Serialize`/`Deserialize` for the public data types (ranges/items/state,
+//!   `VirtualizerOptionsConfig`).
 //! - `tracing`: Emits internal trace/debug/warn events via `tracing` (requires `std`).
+//! - `fixed`: Adds `VirtualizerFixed<N>`, an allocation-free fixed-capacity virtualizer for
+//!   targets without an allocator (core query APIs only, no key cache).
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 
@@ -29,7 +32,10 @@ mod macros;
 
 mod emitter;
 mod fenwick;
+#[cfg(feature = "fixed")]
+mod fixed;
 mod key;
+mod masonry;
 mod options;
 mod state;
 mod types;
@@ -38,16 +44,23 @@ mod virtualizer;
 #[cfg(test)]
 mod tests;
 
-pub use emitter::IndexEmitter;
+pub use emitter::{BufferedIndexEmitter, IndexEmitter};
+#[cfg(feature = "fixed")]
+pub use fixed::VirtualizerFixed;
+pub use masonry::{MasonryItem, MasonryVirtualizer};
 pub use options::{
-    InitialOffset, OnChangeCallback, RangeExtractor,
-    ShouldAdjustScrollPositionOnItemSizeChangeCallback, VirtualizerOptions,
+    EstimateSizeCtx, InitialOffset, OnChangeCallback, OnChangeWithReasonCallback,
+    OnStructuralChangeCallback, RangeExtractor, ShouldAdjustScrollPositionOnItemSizeChangeCallback,
+    VirtualizerOptions, VirtualizerOptionsConfig,
 };
-pub use state::{FrameState, ScrollState, ViewportState};
+pub use state::{DebugReport, FrameState, ScrollState, ViewportState};
 pub use types::{
-    Align, ItemKey, Range, Rect, ScrollDirection, VirtualItem, VirtualItemKeyed, VirtualRange,
+    Align, ChangeReason, EstimateCtx, HitRegion, ItemKey, OptionsChange, Range, Rect, Rounding,
+    ScrollDirection, StructuralChange, UniformWindow, VirtualItem, VirtualItemKeyed, VirtualRange,
+    VirtualizerStatus,
 };
 pub use virtualizer::Virtualizer;
 
 #[doc(hidden)]
 pub use key::KeyCacheKey;
+pub use key::{FnvKey, hash_key};
@@ -0,0 +1,317 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::Rect;
+
+/// A positioned item produced by [`MasonryVirtualizer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MasonryItem {
+    pub index: usize,
+    pub column: usize,
+    /// Cross-axis offset of the column (`column * (column_width + gap)`).
+    pub x: u32,
+    /// Main-axis offset (includes `padding_start`).
+    pub y: u64,
+    /// Cross-axis size (the column width).
+    pub width: u32,
+    /// Main-axis size (excludes `gap`).
+    pub height: u32,
+}
+
+impl MasonryItem {
+    /// Returns `y + height`.
+    pub fn end(&self) -> u64 {
+        self.y.saturating_add(self.height as u64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Slot {
+    column: usize,
+    y: u64,
+}
+
+/// A headless multi-column "masonry" layout (fixed column count, variable item heights).
+///
+/// Items are placed in index order, each into the currently shortest column (ties go to the
+/// leftmost column). The main axis is virtualized: [`Self::for_each_virtual_item`] yields the
+/// items whose vertical span intersects the viewport (plus `overscan` items per column).
+///
+/// Measurement updates reflow the layout from the changed index onward, since later items may
+/// move to a different column. Range queries run in `O(columns * log n)`.
+#[derive(Clone)]
+pub struct MasonryVirtualizer {
+    count: usize,
+    columns: usize,
+    estimate_height: Arc<dyn Fn(usize) -> u32 + Send + Sync>,
+    /// Gap between rows and between columns.
+    gap: u32,
+    padding_start: u32,
+    padding_end: u32,
+    overscan: usize,
+    rect: Rect,
+    scroll_offset: u64,
+
+    heights: Vec<u32>,
+    slots: Vec<Slot>,
+    // Per-column item indexes, in ascending index (and therefore `y`) order.
+    column_items: Vec<Vec<usize>>,
+    column_ends: Vec<u64>,
+}
+
+impl MasonryVirtualizer {
+    /// Creates a masonry layout with `columns` columns (at least 1).
+    pub fn new(
+        count: usize,
+        columns: usize,
+        estimate_height: impl Fn(usize) -> u32 + Send + Sync + 'static,
+    ) -> Self {
+        let mut m = Self {
+            count,
+            columns: columns.max(1),
+            estimate_height: Arc::new(estimate_height),
+            gap: 0,
+            padding_start: 0,
+            padding_end: 0,
+            overscan: 1,
+            rect: Rect::default(),
+            scroll_offset: 0,
+            heights: Vec::new(),
+            slots: Vec::new(),
+            column_items: Vec::new(),
+            column_ends: Vec::new(),
+        };
+        m.heights = (0..count).map(|i| (m.estimate_height)(i)).collect();
+        m.reflow_from(0);
+        m
+    }
+
+    pub fn with_gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self.reflow_from(0);
+        self
+    }
+
+    pub fn with_padding(mut self, padding_start: u32, padding_end: u32) -> Self {
+        self.padding_start = padding_start;
+        self.padding_end = padding_end;
+        self.reflow_from(0);
+        self
+    }
+
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn scroll_offset(&self) -> u64 {
+        self.scroll_offset
+    }
+
+    pub fn scroll_rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Sets the viewport (`main` = height, `cross` = width used to size columns).
+    pub fn set_scroll_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    pub fn set_scroll_offset(&mut self, scroll_offset: u64) {
+        self.scroll_offset = scroll_offset;
+    }
+
+    pub fn set_scroll_offset_clamped(&mut self, scroll_offset: u64) {
+        self.scroll_offset = scroll_offset.min(self.max_scroll_offset());
+    }
+
+    pub fn set_columns(&mut self, columns: usize) {
+        let columns = columns.max(1);
+        if self.columns == columns {
+            return;
+        }
+        self.columns = columns;
+        self.reflow_from(0);
+    }
+
+    /// Updates the item count. New items use `estimate_height`.
+    pub fn set_count(&mut self, count: usize) {
+        if self.count == count {
+            return;
+        }
+        let prev = self.count;
+        self.count = count;
+        if count > prev {
+            for i in prev..count {
+                self.heights.push((self.estimate_height)(i));
+            }
+            self.reflow_from(prev);
+        } else {
+            self.heights.truncate(count);
+            self.reflow_from(count);
+        }
+    }
+
+    /// Updates an item height and reflows the layout from `index` onward.
+    ///
+    /// Returns `true` if the height changed.
+    pub fn measure(&mut self, index: usize, height: u32) -> bool {
+        if index >= self.count || self.heights[index] == height {
+            return false;
+        }
+        self.heights[index] = height;
+        self.reflow_from(index);
+        true
+    }
+
+    /// Returns the width of each column, derived from `scroll_rect().cross`.
+    pub fn column_width(&self) -> u32 {
+        let cols = self.columns as u32;
+        let gaps = self.gap.saturating_mul(cols.saturating_sub(1));
+        self.rect.cross.saturating_sub(gaps) / cols.max(1)
+    }
+
+    /// Returns the main-axis size of the tallest column (including padding).
+    pub fn total_size(&self) -> u64 {
+        let content = self
+            .column_ends
+            .iter()
+            .map(|&end| end.saturating_sub(self.padding_start as u64))
+            .max()
+            .unwrap_or(0);
+        (self.padding_start as u64)
+            .saturating_add(content)
+            .saturating_add(self.padding_end as u64)
+    }
+
+    pub fn max_scroll_offset(&self) -> u64 {
+        self.total_size().saturating_sub(self.rect.main as u64)
+    }
+
+    pub fn item(&self, index: usize) -> Option<MasonryItem> {
+        (index < self.count).then(|| self.item_unchecked(index))
+    }
+
+    /// Iterates the items intersecting the viewport (plus `overscan` items per column).
+    ///
+    /// Items are yielded column by column (left to right), each column in ascending `y` order.
+    pub fn for_each_virtual_item(&self, f: impl FnMut(MasonryItem)) {
+        self.for_each_virtual_item_for(self.scroll_offset, self.rect.main, f);
+    }
+
+    pub fn for_each_virtual_item_for(
+        &self,
+        scroll_offset: u64,
+        viewport_size: u32,
+        mut f: impl FnMut(MasonryItem),
+    ) {
+        if viewport_size == 0 {
+            return;
+        }
+        let view_start = scroll_offset;
+        let view_end = scroll_offset.saturating_add(viewport_size as u64);
+        for items in &self.column_items {
+            // First item whose end is past the viewport start.
+            let first = items.partition_point(|&i| {
+                self.slots[i].y.saturating_add(self.heights[i] as u64) <= view_start
+            });
+            // First item starting at or after the viewport end.
+            let last = items.partition_point(|&i| self.slots[i].y < view_end);
+            if first >= last {
+                continue;
+            }
+            let start = first.saturating_sub(self.overscan);
+            let end = cmp::min(items.len(), last.saturating_add(self.overscan));
+            for &i in &items[start..end] {
+                f(self.item_unchecked(i));
+            }
+        }
+    }
+
+    fn item_unchecked(&self, index: usize) -> MasonryItem {
+        let slot = self.slots[index];
+        let width = self.column_width();
+        MasonryItem {
+            index,
+            column: slot.column,
+            x: (slot.column as u32).saturating_mul(width.saturating_add(self.gap)),
+            y: slot.y,
+            width,
+            height: self.heights[index],
+        }
+    }
+
+    fn reflow_from(&mut self, from: usize) {
+        let from = from.min(self.count);
+        let padding_start = self.padding_start as u64;
+
+        if from == 0 || self.column_items.len() != self.columns {
+            self.column_items = alloc::vec![Vec::new(); self.columns];
+            self.column_ends = alloc::vec![padding_start; self.columns];
+            self.slots.clear();
+            self.lay_out(0);
+            return;
+        }
+
+        for (col, items) in self.column_items.iter_mut().enumerate() {
+            let keep = items.partition_point(|&i| i < from);
+            items.truncate(keep);
+            self.column_ends[col] = match items.last() {
+                Some(&i) => self.slots[i]
+                    .y
+                    .saturating_add(self.heights[i] as u64)
+                    .saturating_add(self.gap as u64),
+                None => padding_start,
+            };
+        }
+        self.slots.truncate(from);
+        self.lay_out(from);
+    }
+
+    fn lay_out(&mut self, from: usize) {
+        for i in from..self.count {
+            let (column, &y) = self
+                .column_ends
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, end)| *end)
+                .unwrap_or((0, &0));
+            self.slots.push(Slot { column, y });
+            self.column_items[column].push(i);
+            self.column_ends[column] = y
+                .saturating_add(self.heights[i] as u64)
+                .saturating_add(self.gap as u64);
+        }
+        // Drop the trailing gap so `column_ends` are the real content ends.
+        for (col, items) in self.column_items.iter().enumerate() {
+            if let Some(&i) = items.last() {
+                self.column_ends[col] = self.slots[i].y.saturating_add(self.heights[i] as u64);
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for MasonryVirtualizer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MasonryVirtualizer")
+            .field("count", &self.count)
+            .field("columns", &self.columns)
+            .field("gap", &self.gap)
+            .field("padding_start", &self.padding_start)
+            .field("padding_end", &self.padding_end)
+            .field("overscan", &self.overscan)
+            .field("rect", &self.rect)
+            .field("scroll_offset", &self.scroll_offset)
+            .finish_non_exhaustive()
+    }
+}
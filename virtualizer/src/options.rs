@@ -1,13 +1,25 @@
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 
 use crate::virtualizer::Virtualizer;
-use crate::{ItemKey, Range, Rect, VirtualItem};
+use crate::{
+    ChangeReason, EstimateCtx, ItemKey, Range, Rect, Rounding, StructuralChange, VirtualItem,
+};
 
 /// A callback fired when a virtualizer state update occurs.
 ///
 /// The second argument is `is_scrolling`.
 pub type OnChangeCallback<K> = Arc<dyn Fn(&Virtualizer<K>, bool) + Send + Sync>;
 
+/// A callback fired when a virtualizer state update occurs, along with why it fired.
+///
+/// This lets adapters skip layout work for changes that don't affect it (e.g.
+/// [`ChangeReason::ScrollingFlag`]).
+pub type OnChangeWithReasonCallback<K> = Arc<dyn Fn(&Virtualizer<K>, ChangeReason) + Send + Sync>;
+
+/// A callback fired when per-index layout state is rebuilt, along with the cause.
+pub type OnStructuralChangeCallback = Arc<dyn Fn(StructuralChange) + Send + Sync>;
+
 /// A hook that decides whether to adjust scroll position when an item size changes.
 ///
 /// This is typically used to prevent visual "jumps" when an item above the current scroll offset
@@ -28,6 +40,10 @@ pub type ShouldAdjustScrollPositionOnItemSizeChangeCallback<K> =
 /// debug builds).
 pub type RangeExtractor = Arc<dyn Fn(Range, &mut dyn FnMut(usize)) + Send + Sync>;
 
+/// A context-aware size estimate: `(absolute_index, ctx) -> size` (see
+/// [`VirtualizerOptions::estimate_size_ctx`]).
+pub type EstimateSizeCtx = Arc<dyn Fn(usize, &EstimateCtx<'_>) -> u32 + Send + Sync>;
+
 /// Initial scroll offset configuration.
 #[derive(Clone)]
 pub enum InitialOffset {
@@ -35,6 +51,12 @@ pub enum InitialOffset {
     Value(u64),
     /// A lazily evaluated initial offset provider (called by `Virtualizer::new`).
     Provider(Arc<dyn Fn() -> u64 + Send + Sync>),
+    /// Start scrolled to the end (e.g. log viewers and chats).
+    ///
+    /// Sizes and the viewport are usually unknown at construction time, so this resolves to 0
+    /// until the first non-zero viewport is set (`set_viewport_size`/`set_scroll_rect`, or
+    /// `initial_rect`), which then scrolls to `max_scroll_offset`.
+    End,
 }
 
 impl InitialOffset {
@@ -42,6 +64,7 @@ impl InitialOffset {
         match self {
             Self::Value(v) => *v,
             Self::Provider(f) => f(),
+            Self::End => 0,
         }
     }
 }
@@ -57,6 +80,7 @@ impl core::fmt::Debug for InitialOffset {
         match self {
             Self::Value(v) => f.debug_tuple("Value").field(v).finish(),
             Self::Provider(_) => f.write_str("Provider(..)"),
+            Self::End => f.write_str("End"),
         }
     }
 }
@@ -67,7 +91,35 @@ impl core::fmt::Debug for InitialOffset {
 /// update a few fields and call `Virtualizer::set_options` without reallocating closures.
 pub struct VirtualizerOptions<K = ItemKey> {
     pub count: usize,
+    /// Absolute index of the first item exposed to the virtualizer (default: 0).
+    ///
+    /// This enables windowed virtualization over a sub-slice of a larger dataset: the virtualizer
+    /// tracks `count` items (`window_base..window_base + count`), while `estimate_size` and
+    /// `get_item_key` receive *absolute* indexes. All other index-based APIs use window-local
+    /// indexes (`0..count`); see `Virtualizer::absolute_index` and `Virtualizer::rebase_window`.
+    pub window_base: usize,
+    /// Returns the estimated size of an item in the scroll axis.
+    ///
+    /// Zero is a valid size (e.g. empty placeholders). Zero-sized items never panic or produce
+    /// out-of-bounds ranges; if *every* item is zero-sized, `total_size` and `max_scroll_offset`
+    /// are 0 and the visible range is empty.
     pub estimate_size: Arc<dyn Fn(usize) -> u32 + Send + Sync>,
+
+    /// Optional context-aware estimate (default: `None`).
+    ///
+    /// When set, unmeasured items are estimated with this closure instead of `estimate_size`. It
+    /// receives the absolute index plus an [`EstimateCtx`] exposing the nearest measured
+    /// neighbours, which enables locality-based (e.g. interpolated) estimates. Neighbour lookups
+    /// scan linearly, so prefer this for lists where measured items are reasonably dense.
+    pub estimate_size_ctx: Option<EstimateSizeCtx>,
+
+    /// Uniform estimate fast path (default: `None`).
+    ///
+    /// When set, every unmeasured item is estimated as this size and `estimate_size` is never
+    /// called. Rebuilds of a list without cached measurements then skip the per-item
+    /// `estimate_size` and `get_item_key` calls entirely. `estimate_size_ctx` still takes
+    /// precedence when set.
+    pub uniform_estimate: Option<u32>,
     pub get_item_key: Arc<dyn Fn(usize) -> K + Send + Sync>,
     /// Optional index selection hook.
     ///
@@ -78,11 +130,42 @@ pub struct VirtualizerOptions<K = ItemKey> {
     /// must emit a sorted (ascending) sequence of indexes. Duplicates are allowed but ignored.
     pub range_extractor: Option<RangeExtractor>,
 
+    /// Validates `range_extractor` output at runtime (including release builds).
+    ///
+    /// When enabled, out-of-order/out-of-bounds indexes are skipped, logged (with
+    /// `feature = "tracing"`), and counted in `Virtualizer::extractor_violations` instead of
+    /// triggering debug assertions.
+    pub validate_extractor: bool,
+
     /// Enables/disables the virtualizer. When disabled, query methods return empty results.
     pub enabled: bool,
 
     pub overscan: usize,
 
+    /// Overscan in pixels instead of items (default: `None`).
+    ///
+    /// When set, this overrides `overscan`: the rendered range extends until it covers
+    /// `overscan_px` of content on each side of the visible range (items partially inside the band
+    /// are included). This keeps the pre-render distance predictable when item sizes vary widely.
+    /// `range_extractor`s still receive `Range::overscan` (the item count).
+    pub overscan_px: Option<u32>,
+
+    /// Extra items beyond `overscan` that adapters may render as cheap placeholders.
+    ///
+    /// See `Virtualizer::overscan_bands`. Defaults to `0` (no placeholder band).
+    pub placeholder_overscan: usize,
+
+    /// Sorted (window-local) indexes of items rendered as overlays outside the scroll content, e.g.
+    /// true sticky headers (default: empty).
+    ///
+    /// Pinned items take no space in the layout (no size, no gap) and do not affect `total_size` or
+    /// offsets. They are skipped by the `for_each_virtual_*` iterators and reported by
+    /// `Virtualizer::for_each_pinned_item` instead, sized from their measurement or
+    /// `estimate_size`. Unlike indexes pinned by a `range_extractor`, they are not part of the
+    /// scrollable flow. Measuring a pinned item updates its overlay size but reports no layout
+    /// change.
+    pub always_pinned: Vec<usize>,
+
     /// The initial size of the scrollable area (aka TanStack Virtual `initialRect`).
     ///
     /// This is a platform-agnostic rect where:
@@ -100,11 +183,29 @@ pub struct VirtualizerOptions<K = ItemKey> {
     /// Additional padding applied when computing scroll-to offsets.
     pub scroll_padding_end: u32,
 
+    /// Rounding for `Align::Center` targets that fall on a half pixel (default:
+    /// `Rounding::HalfUp`).
+    ///
+    /// The centered offset is `(start + end - viewport) / 2`; e.g. an 11px item in a 10px viewport
+    /// would be centered at `start + 0.5`.
+    pub center_rounding: Rounding,
+
     /// Where the list starts inside the scroll element (aka TanStack Virtual `scrollMargin`).
     ///
     /// This is useful when the scroll offset is measured from a larger scroll container (e.g.
     /// window scrolling) while the list begins after some header/content.
-    pub scroll_margin: u32,
+    ///
+    /// May be negative when the list begins *before* the scroll origin (e.g. part of the list is
+    /// above the measured container start); item starts then saturate at 0.
+    pub scroll_margin: i64,
+
+    /// Device-pixel grid that `set_scroll_offset` snaps offsets to (default: `None`).
+    ///
+    /// On HiDPI displays, set this to the size of one device pixel in your offset units (e.g. `2`
+    /// when offsets are in physical pixels at 2x, or a layout unit grid) to avoid shimmer on
+    /// sub-pixel item boundaries. `None`, `0` and `1` disable snapping. See
+    /// `Virtualizer::snap_offset_to_grid`.
+    pub scroll_grid: Option<u32>,
 
     /// Initial scroll offset (aka TanStack Virtual `initialOffset`).
     pub initial_offset: InitialOffset,
@@ -114,6 +215,16 @@ pub struct VirtualizerOptions<K = ItemKey> {
     /// The `sync` argument indicates whether a scroll is in progress.
     pub on_change: Option<OnChangeCallback<K>>,
 
+    /// Optional callback fired alongside `on_change`, receiving a [`ChangeReason`].
+    pub on_change_with_reason: Option<OnChangeWithReasonCallback<K>>,
+
+    /// Optional callback fired when per-index layout state is rebuilt (default: `None`).
+    ///
+    /// Unlike `on_change`, this only fires for structural changes (`count`, estimates/key mapping,
+    /// gaps), never for scrolling or individual measurements, so adapters can use it to invalidate
+    /// index-keyed caches. It fires immediately, even inside `batch_update`.
+    pub on_structural_change: Option<OnStructuralChangeCallback>,
+
     /// Determines whether to use a native scrollend event to detect when scrolling has stopped.
     ///
     /// This is included for TanStack Virtual parity. In this crate, scrolling state is driven
@@ -128,33 +239,141 @@ pub struct VirtualizerOptions<K = ItemKey> {
     pub should_adjust_scroll_position_on_item_size_change:
         Option<ShouldAdjustScrollPositionOnItemSizeChangeCallback<K>>,
 
+    /// Global switch for scroll adjustment on item resize (default: `true`).
+    ///
+    /// When `false`, `measure`/`resize_item*` never mutate `scroll_offset`, regardless of
+    /// `should_adjust_scroll_position_on_item_size_change`. Useful when your UI backend
+    /// repositions the scroll container itself.
+    pub adjust_scroll_on_resize: bool,
+
+    /// Ignore re-measurement of already measured items (default: `false`).
+    ///
+    /// When `true`, once an index is measured (or resized), subsequent `measure`/`resize_item*`
+    /// calls for it are no-ops. Useful for immutable content that only needs a single measurement
+    /// pass.
+    pub freeze_measured: bool,
+
+    /// Treat `count` as provisional (default: `false`), e.g. a guessed row count shown as skeleton
+    /// rows while the real count loads.
+    ///
+    /// The count is laid out normally, but all measurements (`measure*`, `resize_item*`) are
+    /// ignored so placeholder keys from `get_item_key` never pollute the measurement cache. Clear
+    /// it with `Virtualizer::set_provisional(false)` once the real data is in place.
+    pub provisional: bool,
+
+    /// Re-clamp `scroll_offset` to `max_scroll_offset` after a measurement shrinks the list
+    /// (default: `false`).
+    ///
+    /// Without this, items shrinking on measurement can leave `scroll_offset` past the end (a blank
+    /// viewport) until the next scroll event. Applies to `measure*`, `resize_item*` and
+    /// `apply_measurements`.
+    pub clamp_scroll_after_measure: bool,
+
+    /// Lower bound applied to every estimated/measured size before it enters the layout
+    /// (default: 0).
+    pub min_item_size: u32,
+
+    /// Upper bound applied to every estimated/measured size before it enters the layout
+    /// (default: `None`).
+    ///
+    /// This defends the layout against bogus measurements (e.g. `u32::MAX / 2`). If both bounds are
+    /// set and conflict, `max_item_size` wins.
+    pub max_item_size: Option<u32>,
+
+    /// Reject measurements larger than `outlier_factor` times the running mean of accepted
+    /// measurements (default: `None`).
+    ///
+    /// A softer, distribution-adaptive alternative to `max_item_size` for defending against
+    /// corrupted sizes. Rejected measurements leave the item unchanged and are counted by
+    /// `Virtualizer::rejected_measurements`. An item's first measurement and any later size change
+    /// feed the mean (identical re-measures and `always_pinned` items don't). The guard only
+    /// applies once at least one such measurement has been accepted.
+    pub outlier_factor: Option<f32>,
+
+    /// Maintain an internal key → index map (default: `false`).
+    ///
+    /// When `true`, the virtualizer rebuilds the map whenever `get_item_key`, `count` or the window
+    /// change (and on `sync_item_keys`), enabling `Virtualizer::index_for_key` and key-based
+    /// helpers without an adapter-side lookup. Costs one map entry per item. Keys should be unique;
+    /// with duplicates, the last index wins.
+    pub maintain_key_index: bool,
+
     /// Space between items.
     pub gap: u32,
+
+    /// Gap before the first item (inside `padding_start`).
+    ///
+    /// Together with `trailing_gap`, this models CSS-style item margins at the list edges. Like
+    /// `gap`, it participates in `total_size` and item offsets; unlike padding, it is revealed by
+    /// `Virtualizer::scroll_to_index_offset_with_gap` for the first item. Plain
+    /// `scroll_to_index_offset` aligns the item itself (the edge gap is not revealed).
+    pub leading_gap: u32,
+    /// Gap after the last item (inside `padding_end`). See `leading_gap`.
+    pub trailing_gap: u32,
+
+    /// Optional per-item gap: returns the space placed after item `i`.
+    ///
+    /// When set, this takes precedence over `gap`. The value returned for the last item is ignored
+    /// (there is no trailing gap).
+    pub gap_after: Option<Arc<dyn Fn(usize) -> u32 + Send + Sync>>,
+
+    /// Optional cap for the rendered scroll extent.
+    ///
+    /// Some UI backends cannot handle scroll containers larger than a certain size (e.g. ~33M
+    /// pixels). When the true total size exceeds this cap, use `Virtualizer::rendered_total_size`
+    /// for the container size and convert offsets with `Virtualizer::to_rendered` /
+    /// `Virtualizer::to_virtual` at the adapter boundary.
+    ///
+    /// All other virtualizer APIs keep operating in "virtual" (uncompressed) space.
+    pub max_total_size: Option<u64>,
 }
 
 impl<K> Clone for VirtualizerOptions<K> {
     fn clone(&self) -> Self {
         Self {
             count: self.count,
+            window_base: self.window_base,
             estimate_size: Arc::clone(&self.estimate_size),
+            estimate_size_ctx: self.estimate_size_ctx.clone(),
+            uniform_estimate: self.uniform_estimate,
             get_item_key: Arc::clone(&self.get_item_key),
             range_extractor: self.range_extractor.clone(),
+            validate_extractor: self.validate_extractor,
             enabled: self.enabled,
             overscan: self.overscan,
+            overscan_px: self.overscan_px,
+            placeholder_overscan: self.placeholder_overscan,
+            always_pinned: self.always_pinned.clone(),
             initial_rect: self.initial_rect,
             padding_start: self.padding_start,
             padding_end: self.padding_end,
             scroll_padding_start: self.scroll_padding_start,
             scroll_padding_end: self.scroll_padding_end,
+            center_rounding: self.center_rounding,
             scroll_margin: self.scroll_margin,
+            scroll_grid: self.scroll_grid,
             initial_offset: self.initial_offset.clone(),
             on_change: self.on_change.clone(),
+            on_change_with_reason: self.on_change_with_reason.clone(),
+            on_structural_change: self.on_structural_change.clone(),
             use_scrollend_event: self.use_scrollend_event,
             is_scrolling_reset_delay_ms: self.is_scrolling_reset_delay_ms,
             should_adjust_scroll_position_on_item_size_change: self
                 .should_adjust_scroll_position_on_item_size_change
                 .clone(),
+            adjust_scroll_on_resize: self.adjust_scroll_on_resize,
+            freeze_measured: self.freeze_measured,
+            provisional: self.provisional,
+            clamp_scroll_after_measure: self.clamp_scroll_after_measure,
+            min_item_size: self.min_item_size,
+            max_item_size: self.max_item_size,
+            outlier_factor: self.outlier_factor,
+            maintain_key_index: self.maintain_key_index,
             gap: self.gap,
+            leading_gap: self.leading_gap,
+            trailing_gap: self.trailing_gap,
+            gap_after: self.gap_after.clone(),
+            max_total_size: self.max_total_size,
         }
     }
 }
@@ -167,25 +386,57 @@ impl VirtualizerOptions<ItemKey> {
     pub fn new(count: usize, estimate_size: impl Fn(usize) -> u32 + Send + Sync + 'static) -> Self {
         Self {
             count,
+            window_base: 0,
             estimate_size: Arc::new(estimate_size),
+            estimate_size_ctx: None,
+            uniform_estimate: None,
             get_item_key: Arc::new(|i| i as u64),
             range_extractor: None,
+            validate_extractor: false,
             enabled: true,
             overscan: 1,
+            overscan_px: None,
+            placeholder_overscan: 0,
+            always_pinned: Vec::new(),
             initial_rect: None,
             padding_start: 0,
             padding_end: 0,
             scroll_padding_start: 0,
             scroll_padding_end: 0,
+            center_rounding: Rounding::HalfUp,
             scroll_margin: 0,
+            scroll_grid: None,
             initial_offset: InitialOffset::default(),
             on_change: None,
+            on_change_with_reason: None,
+            on_structural_change: None,
             use_scrollend_event: false,
             is_scrolling_reset_delay_ms: 150,
             should_adjust_scroll_position_on_item_size_change: None,
+            adjust_scroll_on_resize: true,
+            freeze_measured: false,
+            provisional: false,
+            clamp_scroll_after_measure: false,
+            min_item_size: 0,
+            max_item_size: None,
+            outlier_factor: None,
+            maintain_key_index: false,
             gap: 0,
+            leading_gap: 0,
+            trailing_gap: 0,
+            gap_after: None,
+            max_total_size: None,
         }
     }
+
+    /// Creates options for a list whose item sizes are known up front.
+    ///
+    /// `count` is derived from `sizes.len()` and `estimate_size(i)` returns `sizes[i]`. Combine
+    /// with `Virtualizer::from_exact_sizes` to also mark every item as measured.
+    pub fn from_sizes(sizes: Vec<u32>) -> Self {
+        let count = sizes.len();
+        Self::new(count, move |i| sizes.get(i).copied().unwrap_or(0))
+    }
 }
 
 impl<K> VirtualizerOptions<K> {
@@ -200,26 +451,54 @@ impl<K> VirtualizerOptions<K> {
     ) -> Self {
         Self {
             count,
+            window_base: 0,
             estimate_size: Arc::new(estimate_size),
+            estimate_size_ctx: None,
+            uniform_estimate: None,
             get_item_key: Arc::new(get_item_key),
             range_extractor: None,
+            validate_extractor: false,
             enabled: true,
             overscan: 1,
+            overscan_px: None,
+            placeholder_overscan: 0,
+            always_pinned: Vec::new(),
             initial_rect: None,
             padding_start: 0,
             padding_end: 0,
             scroll_padding_start: 0,
             scroll_padding_end: 0,
+            center_rounding: Rounding::HalfUp,
             scroll_margin: 0,
+            scroll_grid: None,
             initial_offset: InitialOffset::default(),
             on_change: None,
+            on_change_with_reason: None,
+            on_structural_change: None,
             use_scrollend_event: false,
             is_scrolling_reset_delay_ms: 150,
             should_adjust_scroll_position_on_item_size_change: None,
+            adjust_scroll_on_resize: true,
+            freeze_measured: false,
+            provisional: false,
+            clamp_scroll_after_measure: false,
+            min_item_size: 0,
+            max_item_size: None,
+            outlier_factor: None,
+            maintain_key_index: false,
             gap: 0,
+            leading_gap: 0,
+            trailing_gap: 0,
+            gap_after: None,
+            max_total_size: None,
         }
     }
 
+    pub fn with_window_base(mut self, window_base: usize) -> Self {
+        self.window_base = window_base;
+        self
+    }
+
     pub fn with_get_item_key(
         mut self,
         get_item_key: impl Fn(usize) -> K + Send + Sync + 'static,
@@ -236,6 +515,11 @@ impl<K> VirtualizerOptions<K> {
         self
     }
 
+    pub fn with_validate_extractor(mut self, validate_extractor: bool) -> Self {
+        self.validate_extractor = validate_extractor;
+        self
+    }
+
     pub fn with_enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
         self
@@ -252,6 +536,24 @@ impl<K> VirtualizerOptions<K> {
         self
     }
 
+    pub fn with_overscan_px(mut self, overscan_px: Option<u32>) -> Self {
+        self.overscan_px = overscan_px;
+        self
+    }
+
+    pub fn with_placeholder_overscan(mut self, placeholder_overscan: usize) -> Self {
+        self.placeholder_overscan = placeholder_overscan;
+        self
+    }
+
+    /// Sets `always_pinned` (sorted and deduplicated).
+    pub fn with_always_pinned(mut self, mut always_pinned: Vec<usize>) -> Self {
+        always_pinned.sort_unstable();
+        always_pinned.dedup();
+        self.always_pinned = always_pinned;
+        self
+    }
+
     pub fn with_padding(mut self, padding_start: u32, padding_end: u32) -> Self {
         self.padding_start = padding_start;
         self.padding_end = padding_end;
@@ -268,11 +570,21 @@ impl<K> VirtualizerOptions<K> {
         self
     }
 
-    pub fn with_scroll_margin(mut self, scroll_margin: u32) -> Self {
+    pub fn with_center_rounding(mut self, center_rounding: Rounding) -> Self {
+        self.center_rounding = center_rounding;
+        self
+    }
+
+    pub fn with_scroll_margin(mut self, scroll_margin: i64) -> Self {
         self.scroll_margin = scroll_margin;
         self
     }
 
+    pub fn with_scroll_grid(mut self, scroll_grid: Option<u32>) -> Self {
+        self.scroll_grid = scroll_grid;
+        self
+    }
+
     pub fn with_initial_offset(mut self, initial_offset: InitialOffset) -> Self {
         self.initial_offset = initial_offset;
         self
@@ -299,6 +611,22 @@ impl<K> VirtualizerOptions<K> {
         self
     }
 
+    pub fn with_on_change_with_reason(
+        mut self,
+        on_change: Option<impl Fn(&Virtualizer<K>, ChangeReason) + Send + Sync + 'static>,
+    ) -> Self {
+        self.on_change_with_reason = on_change.map(|f| Arc::new(f) as _);
+        self
+    }
+
+    pub fn with_on_structural_change(
+        mut self,
+        on_structural_change: Option<impl Fn(StructuralChange) + Send + Sync + 'static>,
+    ) -> Self {
+        self.on_structural_change = on_structural_change.map(|f| Arc::new(f) as _);
+        self
+    }
+
     pub fn with_use_scrollend_event(mut self, use_scrollend_event: bool) -> Self {
         self.use_scrollend_event = use_scrollend_event;
         self
@@ -317,31 +645,291 @@ impl<K> VirtualizerOptions<K> {
         self
     }
 
+    pub fn with_adjust_scroll_on_resize(mut self, adjust_scroll_on_resize: bool) -> Self {
+        self.adjust_scroll_on_resize = adjust_scroll_on_resize;
+        self
+    }
+
+    pub fn with_freeze_measured(mut self, freeze_measured: bool) -> Self {
+        self.freeze_measured = freeze_measured;
+        self
+    }
+
+    pub fn with_provisional(mut self, provisional: bool) -> Self {
+        self.provisional = provisional;
+        self
+    }
+
+    pub fn with_clamp_scroll_after_measure(mut self, clamp_scroll_after_measure: bool) -> Self {
+        self.clamp_scroll_after_measure = clamp_scroll_after_measure;
+        self
+    }
+
+    pub fn with_maintain_key_index(mut self, maintain_key_index: bool) -> Self {
+        self.maintain_key_index = maintain_key_index;
+        self
+    }
+
+    pub fn with_outlier_factor(mut self, outlier_factor: Option<f32>) -> Self {
+        self.outlier_factor = outlier_factor;
+        self
+    }
+
+    /// Sets `min_item_size` and `max_item_size`.
+    pub fn with_item_size_bounds(mut self, min_item_size: u32, max_item_size: Option<u32>) -> Self {
+        self.min_item_size = min_item_size;
+        self.max_item_size = max_item_size;
+        self
+    }
+
     pub fn with_gap(mut self, gap: u32) -> Self {
         self.gap = gap;
         self
     }
+
+    pub fn with_edge_gaps(mut self, leading_gap: u32, trailing_gap: u32) -> Self {
+        self.leading_gap = leading_gap;
+        self.trailing_gap = trailing_gap;
+        self
+    }
+
+    /// Sets (or clears) the context-aware estimate (see
+    /// [`VirtualizerOptions::estimate_size_ctx`]).
+    pub fn with_estimate_size_ctx(
+        mut self,
+        estimate_size_ctx: Option<impl Fn(usize, &EstimateCtx<'_>) -> u32 + Send + Sync + 'static>,
+    ) -> Self {
+        self.estimate_size_ctx = estimate_size_ctx.map(|f| Arc::new(f) as _);
+        self
+    }
+
+    /// Estimates every unmeasured item as `size` without calling `estimate_size` (see
+    /// [`VirtualizerOptions::uniform_estimate`]).
+    pub fn with_uniform_estimate(mut self, size: u32) -> Self {
+        self.uniform_estimate = Some(size);
+        self
+    }
+
+    /// Sets a per-item gap (see [`VirtualizerOptions::gap_after`]).
+    pub fn with_gap_after(
+        mut self,
+        gap_after: Option<impl Fn(usize) -> u32 + Send + Sync + 'static>,
+    ) -> Self {
+        self.gap_after = gap_after.map(|f| Arc::new(f) as _);
+        self
+    }
+
+    pub fn with_max_total_size(mut self, max_total_size: Option<u64>) -> Self {
+        self.max_total_size = max_total_size;
+        self
+    }
+}
+
+impl<K> VirtualizerOptions<K> {
+    /// Extracts the plain-data options (see [`VirtualizerOptionsConfig`]).
+    pub fn to_config(&self) -> VirtualizerOptionsConfig {
+        VirtualizerOptionsConfig {
+            enabled: self.enabled,
+            overscan: self.overscan,
+            overscan_px: self.overscan_px,
+            placeholder_overscan: self.placeholder_overscan,
+            initial_rect: self.initial_rect,
+            padding_start: self.padding_start,
+            padding_end: self.padding_end,
+            scroll_padding_start: self.scroll_padding_start,
+            scroll_padding_end: self.scroll_padding_end,
+            center_rounding: self.center_rounding,
+            scroll_margin: self.scroll_margin,
+            scroll_grid: self.scroll_grid,
+            use_scrollend_event: self.use_scrollend_event,
+            is_scrolling_reset_delay_ms: self.is_scrolling_reset_delay_ms,
+            adjust_scroll_on_resize: self.adjust_scroll_on_resize,
+            freeze_measured: self.freeze_measured,
+            provisional: self.provisional,
+            clamp_scroll_after_measure: self.clamp_scroll_after_measure,
+            min_item_size: self.min_item_size,
+            max_item_size: self.max_item_size,
+            outlier_factor: self.outlier_factor,
+            maintain_key_index: self.maintain_key_index,
+            validate_extractor: self.validate_extractor,
+            gap: self.gap,
+            leading_gap: self.leading_gap,
+            trailing_gap: self.trailing_gap,
+            max_total_size: self.max_total_size,
+            uniform_estimate: self.uniform_estimate,
+            initial_offset: match self.initial_offset {
+                InitialOffset::Value(v) => Some(v),
+                _ => None,
+            },
+        }
+    }
+
+    /// Applies the plain-data options from `config`, leaving closures, `count`, `window_base`
+    /// and `always_pinned` untouched.
+    ///
+    /// `initial_offset: None` keeps the current `initial_offset`.
+    pub fn apply_config(&mut self, config: &VirtualizerOptionsConfig) {
+        self.enabled = config.enabled;
+        self.overscan = config.overscan;
+        self.overscan_px = config.overscan_px;
+        self.placeholder_overscan = config.placeholder_overscan;
+        self.initial_rect = config.initial_rect;
+        self.padding_start = config.padding_start;
+        self.padding_end = config.padding_end;
+        self.scroll_padding_start = config.scroll_padding_start;
+        self.scroll_padding_end = config.scroll_padding_end;
+        self.center_rounding = config.center_rounding;
+        self.scroll_margin = config.scroll_margin;
+        self.scroll_grid = config.scroll_grid;
+        self.use_scrollend_event = config.use_scrollend_event;
+        self.is_scrolling_reset_delay_ms = config.is_scrolling_reset_delay_ms;
+        self.adjust_scroll_on_resize = config.adjust_scroll_on_resize;
+        self.freeze_measured = config.freeze_measured;
+        self.provisional = config.provisional;
+        self.clamp_scroll_after_measure = config.clamp_scroll_after_measure;
+        self.min_item_size = config.min_item_size;
+        self.max_item_size = config.max_item_size;
+        self.outlier_factor = config.outlier_factor;
+        self.maintain_key_index = config.maintain_key_index;
+        self.validate_extractor = config.validate_extractor;
+        self.gap = config.gap;
+        self.leading_gap = config.leading_gap;
+        self.trailing_gap = config.trailing_gap;
+        self.max_total_size = config.max_total_size;
+        self.uniform_estimate = config.uniform_estimate;
+        if let Some(v) = config.initial_offset {
+            self.initial_offset = InitialOffset::Value(v);
+        }
+    }
+
+    /// Whether `other` is observably identical: equal plain data and the same closure `Arc`s.
+    pub(crate) fn same_as(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        let initial_offset_same = match (&self.initial_offset, &other.initial_offset) {
+            (InitialOffset::Value(a), InitialOffset::Value(b)) => a == b,
+            (InitialOffset::Provider(a), InitialOffset::Provider(b)) => Arc::ptr_eq(a, b),
+            (InitialOffset::End, InitialOffset::End) => true,
+            _ => false,
+        };
+        self.count == other.count
+            && self.window_base == other.window_base
+            && self.always_pinned == other.always_pinned
+            && initial_offset_same
+            && self.to_config() == other.to_config()
+            && Arc::ptr_eq(&self.estimate_size, &other.estimate_size)
+            && Arc::ptr_eq(&self.get_item_key, &other.get_item_key)
+            && same(&self.estimate_size_ctx, &other.estimate_size_ctx)
+            && same(&self.range_extractor, &other.range_extractor)
+            && same(&self.on_change, &other.on_change)
+            && same(&self.on_change_with_reason, &other.on_change_with_reason)
+            && same(&self.on_structural_change, &other.on_structural_change)
+            && same(
+                &self.should_adjust_scroll_position_on_item_size_change,
+                &other.should_adjust_scroll_position_on_item_size_change,
+            )
+            && same(&self.gap_after, &other.gap_after)
+    }
+}
+
+/// The plain-data ("tunable") subset of [`VirtualizerOptions`], for persisting list configuration.
+///
+/// Closures, data-dependent fields (`count`, `window_base`, `always_pinned`) and non-value
+/// initial offsets are not included. Convert with [`VirtualizerOptions::to_config`] and
+/// [`VirtualizerOptions::apply_config`].
+///
+/// With `feature = "serde"`, this type implements `Serialize`/`Deserialize`; missing fields
+/// deserialize to the `VirtualizerOptions` defaults.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct VirtualizerOptionsConfig {
+    pub enabled: bool,
+    pub overscan: usize,
+    pub overscan_px: Option<u32>,
+    pub placeholder_overscan: usize,
+    pub initial_rect: Option<Rect>,
+    pub padding_start: u32,
+    pub padding_end: u32,
+    pub scroll_padding_start: u32,
+    pub scroll_padding_end: u32,
+    pub center_rounding: Rounding,
+    pub scroll_margin: i64,
+    pub scroll_grid: Option<u32>,
+    pub use_scrollend_event: bool,
+    pub is_scrolling_reset_delay_ms: u64,
+    pub adjust_scroll_on_resize: bool,
+    pub freeze_measured: bool,
+    pub provisional: bool,
+    pub clamp_scroll_after_measure: bool,
+    pub min_item_size: u32,
+    pub max_item_size: Option<u32>,
+    pub outlier_factor: Option<f32>,
+    pub maintain_key_index: bool,
+    pub validate_extractor: bool,
+    pub gap: u32,
+    pub leading_gap: u32,
+    pub trailing_gap: u32,
+    pub max_total_size: Option<u64>,
+    pub uniform_estimate: Option<u32>,
+    /// `Some` for `InitialOffset::Value`, `None` otherwise.
+    pub initial_offset: Option<u64>,
+}
+
+impl Default for VirtualizerOptionsConfig {
+    fn default() -> Self {
+        VirtualizerOptions::new(0, |_| 0).to_config()
+    }
 }
 
 impl<K> core::fmt::Debug for VirtualizerOptions<K> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("VirtualizerOptions")
             .field("count", &self.count)
+            .field("window_base", &self.window_base)
+            .field("estimate_size_ctx", &self.estimate_size_ctx.is_some())
+            .field("uniform_estimate", &self.uniform_estimate)
+            .field("validate_extractor", &self.validate_extractor)
             .field("enabled", &self.enabled)
             .field("overscan", &self.overscan)
+            .field("overscan_px", &self.overscan_px)
+            .field("placeholder_overscan", &self.placeholder_overscan)
+            .field("always_pinned", &self.always_pinned)
             .field("initial_rect", &self.initial_rect)
             .field("padding_start", &self.padding_start)
             .field("padding_end", &self.padding_end)
             .field("scroll_padding_start", &self.scroll_padding_start)
             .field("scroll_padding_end", &self.scroll_padding_end)
+            .field("center_rounding", &self.center_rounding)
             .field("scroll_margin", &self.scroll_margin)
+            .field("scroll_grid", &self.scroll_grid)
             .field("initial_offset", &self.initial_offset)
             .field("use_scrollend_event", &self.use_scrollend_event)
             .field(
                 "is_scrolling_reset_delay_ms",
                 &self.is_scrolling_reset_delay_ms,
             )
+            .field("adjust_scroll_on_resize", &self.adjust_scroll_on_resize)
+            .field("freeze_measured", &self.freeze_measured)
+            .field("provisional", &self.provisional)
+            .field(
+                "clamp_scroll_after_measure",
+                &self.clamp_scroll_after_measure,
+            )
+            .field("min_item_size", &self.min_item_size)
+            .field("max_item_size", &self.max_item_size)
+            .field("outlier_factor", &self.outlier_factor)
+            .field("maintain_key_index", &self.maintain_key_index)
             .field("gap", &self.gap)
+            .field("gap_after", &self.gap_after.is_some())
+            .field("leading_gap", &self.leading_gap)
+            .field("trailing_gap", &self.trailing_gap)
+            .field("max_total_size", &self.max_total_size)
             .finish_non_exhaustive()
     }
 }
@@ -1,4 +1,4 @@
-use crate::Rect;
+use crate::{Rect, ScrollDirection, VirtualRange};
 
 /// A lightweight, serializable snapshot of the current viewport geometry.
 ///
@@ -29,3 +29,47 @@ pub struct FrameState {
     pub viewport: ViewportState,
     pub scroll: ScrollState,
 }
+
+/// A diagnostic snapshot of derived virtualizer state (see `Virtualizer::debug_report`).
+///
+/// Unlike the derived `Debug` of `Virtualizer`, this omits per-item buffers and is meant to be
+/// attached to bug reports. `Display` renders a compact multi-line summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebugReport {
+    pub count: usize,
+    pub enabled: bool,
+    pub viewport_size: u32,
+    pub scroll_offset: u64,
+    pub max_scroll_offset: u64,
+    pub total_size: u64,
+    pub visible_range: VirtualRange,
+    pub virtual_range: VirtualRange,
+    pub measured_count: usize,
+    pub scroll_direction: Option<ScrollDirection>,
+}
+
+impl core::fmt::Display for DebugReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "count: {} (enabled: {})", self.count, self.enabled)?;
+        writeln!(f, "viewport_size: {}", self.viewport_size)?;
+        writeln!(
+            f,
+            "scroll_offset: {} / max {}",
+            self.scroll_offset, self.max_scroll_offset
+        )?;
+        writeln!(f, "total_size: {}", self.total_size)?;
+        writeln!(
+            f,
+            "visible_range: {}..{}",
+            self.visible_range.start_index, self.visible_range.end_index
+        )?;
+        writeln!(
+            f,
+            "virtual_range: {}..{}",
+            self.virtual_range.start_index, self.virtual_range.end_index
+        )?;
+        writeln!(f, "measured: {}/{}", self.measured_count, self.count)?;
+        write!(f, "scroll_direction: {:?}", self.scroll_direction)
+    }
+}
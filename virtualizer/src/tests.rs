@@ -870,7 +870,7 @@ fn property_random_layout_invariants() {
         opts.gap = gap;
         opts.padding_start = padding_start;
         opts.padding_end = padding_end;
-        opts.scroll_margin = scroll_margin;
+        opts.scroll_margin = scroll_margin as i64;
         opts.overscan = overscan;
 
         let mut v = Virtualizer::new(opts);
@@ -1386,3 +1386,3194 @@ fn collect_virtual_items_matches_for_each() {
 
     assert_eq!(a, b);
 }
+
+#[test]
+fn max_total_size_scales_offsets_between_virtual_and_rendered_space() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(2_000_000, |_| 30).with_max_total_size(Some(33_000_000)),
+    );
+    v.set_viewport_size(600);
+
+    assert_eq!(v.total_size(), 60_000_000);
+    assert_eq!(v.rendered_total_size(), 33_000_000);
+    assert!(v.is_scaled());
+
+    // Both ends of the scrollable range map onto each other.
+    assert_eq!(v.to_rendered(0), 0);
+    assert_eq!(v.to_virtual(0), 0);
+    assert_eq!(v.to_rendered(v.max_scroll_offset()), 33_000_000 - 600);
+    assert_eq!(v.to_virtual(33_000_000 - 600), v.max_scroll_offset());
+
+    // Roundtrips lose at most one rendered unit worth of virtual offset.
+    let ratio = v.total_size() / v.rendered_total_size() + 1;
+    for off in [1u64, 12_345, 30_000_000, 59_999_000] {
+        let back = v.to_virtual(v.to_rendered(off));
+        assert!(off.abs_diff(back) <= ratio, "off={off}, back={back}");
+    }
+
+    // The last item is reachable from the rendered max offset.
+    let off = v.to_virtual(v.rendered_total_size() - 600);
+    v.set_scroll_offset_clamped(off);
+    assert_eq!(v.visible_range().end_index, 2_000_000);
+}
+
+#[test]
+fn max_total_size_is_identity_when_not_exceeded() {
+    let mut v =
+        Virtualizer::new(VirtualizerOptions::new(100, |_| 1).with_max_total_size(Some(1000)));
+    v.set_viewport_size(10);
+    assert!(!v.is_scaled());
+    assert_eq!(v.rendered_total_size(), 100);
+    assert_eq!(v.to_rendered(42), 42);
+    assert_eq!(v.to_virtual(42), 42);
+
+    v.set_max_total_size(Some(50));
+    assert!(v.is_scaled());
+    assert_eq!(v.rendered_total_size(), 50);
+    assert_eq!(v.to_rendered(90), 40);
+    assert_eq!(v.to_virtual(40), 90);
+}
+
+#[test]
+fn scroll_to_index_offset_with_gap_reveals_surrounding_gaps() {
+    let mut opts = VirtualizerOptions::new(10, |_| 10);
+    opts.gap = 4; // item i starts at 14 * i
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(30);
+
+    assert_eq!(v.scroll_to_index_offset(3, Align::Start), 42);
+    assert_eq!(v.scroll_to_index_offset_with_gap(3, Align::Start), 38);
+
+    // Item 3 ends at 52; the gap after it ends at 56.
+    assert_eq!(v.scroll_to_index_offset(3, Align::End), 22);
+    assert_eq!(v.scroll_to_index_offset_with_gap(3, Align::End), 26);
+
+    // Index 0 has no preceding gap.
+    assert_eq!(v.scroll_to_index_offset_with_gap(0, Align::Start), 0);
+
+    // The last item has no trailing gap.
+    assert_eq!(
+        v.scroll_to_index_offset_with_gap(9, Align::End),
+        v.scroll_to_index_offset(9, Align::End)
+    );
+}
+
+#[test]
+fn gap_after_supports_mixed_gaps() {
+    // Groups of 3 items: 10 between groups, 1 within a group.
+    let opts = VirtualizerOptions::new(6, |_| 2)
+        .with_gap_after(Some(|i: usize| if i % 3 == 2 { 10 } else { 1 }));
+    let mut v = Virtualizer::new(opts);
+
+    // Layout: 0(0..2) 1(3..5) 2(6..8) [gap 8..18] 3(18..20) 4(21..23) 5(24..26)
+    assert_eq!(v.total_size(), 26);
+    assert_eq!(v.item_start(3), Some(18));
+    assert_eq!(v.item_start(5), Some(24));
+
+    // Gap regions map to the preceding item.
+    assert_eq!(v.index_at_offset(2), Some(0));
+    assert_eq!(v.index_at_offset(8), Some(2));
+    assert_eq!(v.index_at_offset(17), Some(2));
+    assert_eq!(v.index_at_offset(18), Some(3));
+
+    v.set_viewport_size(100);
+    let mut starts = Vec::new();
+    v.for_each_virtual_item(|it| starts.push(it.start));
+    assert_eq!(starts, vec![0, 3, 6, 18, 21, 24]);
+
+    // Count changes keep per-item gap bookkeeping consistent.
+    v.set_count(3);
+    assert_eq!(v.total_size(), 8);
+    v.set_count(4);
+    assert_eq!(v.total_size(), 20);
+
+    // Clearing the closure falls back to the uniform gap.
+    v.set_gap_after(None::<fn(usize) -> u32>);
+    assert_eq!(v.total_size(), 8);
+}
+
+#[test]
+fn debug_positions_covers_full_layout() {
+    let mut opts = VirtualizerOptions::new(20, |i| (i % 5) as u32 + 1);
+    opts.gap = 2;
+    opts.padding_start = 3;
+    opts.padding_end = 7;
+    let mut v = Virtualizer::new(opts);
+    v.measure(4, 11);
+
+    let positions = v.debug_positions();
+    assert_eq!(positions.len(), 20);
+    for (i, &(index, start, size)) in positions.iter().enumerate() {
+        assert_eq!(index, i);
+        assert_eq!(Some(start), v.item_start(i));
+        assert_eq!(Some(size), v.item_size(i));
+    }
+    for w in positions.windows(2) {
+        assert!(w[0].1 < w[1].1);
+    }
+
+    let &(_, last_start, last_size) = positions.last().unwrap();
+    assert_eq!(last_start + last_size as u64 + 7, v.total_size());
+
+    v.set_enabled(false);
+    assert!(v.debug_positions().is_empty());
+}
+
+#[test]
+fn is_index_visible_and_in_overscan_boundaries() {
+    let mut opts = VirtualizerOptions::new(100, |_| 10);
+    opts.scroll_margin = 50;
+    opts.overscan = 2;
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(30);
+
+    // Viewport [0, 30) ends before the list starts at 50.
+    v.set_scroll_offset(0);
+    assert!(!v.is_index_visible(0));
+    assert!(!v.is_index_in_overscan(0));
+
+    // Viewport [70, 100) covers list offsets [20, 50): items 2..5.
+    v.set_scroll_offset(70);
+    assert!(!v.is_index_visible(1));
+    assert!(v.is_index_visible(2));
+    assert!(v.is_index_visible(4));
+    assert!(!v.is_index_visible(5));
+
+    assert!(!v.is_index_in_overscan(usize::MAX));
+    assert!(v.is_index_in_overscan(0));
+    assert!(v.is_index_in_overscan(1));
+    assert!(!v.is_index_in_overscan(2));
+    assert!(!v.is_index_in_overscan(4));
+    assert!(v.is_index_in_overscan(5));
+    assert!(v.is_index_in_overscan(6));
+    assert!(!v.is_index_in_overscan(7));
+
+    v.set_enabled(false);
+    assert!(!v.is_index_visible(0));
+    assert!(!v.is_index_in_overscan(0));
+}
+
+#[test]
+fn from_exact_sizes_marks_all_items_measured() {
+    let sizes = vec![3u32, 7, 1, 0, 12];
+    let calls = Arc::new(AtomicUsize::new(0));
+    let opts = VirtualizerOptions::new(0, {
+        let calls = Arc::clone(&calls);
+        move |_| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            1
+        }
+    })
+    .with_padding(4, 6);
+    let v = Virtualizer::from_exact_sizes(opts, &sizes);
+
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+    assert_eq!(v.count(), 5);
+    assert_eq!(v.total_size(), 4 + 23 + 6);
+    assert_eq!(v.measurement_cache_len(), 0);
+    for (i, &size) in sizes.iter().enumerate() {
+        assert!(v.is_measured(i));
+        assert_eq!(v.item_size(i), Some(size));
+    }
+
+    let v = Virtualizer::new(VirtualizerOptions::from_sizes(sizes.clone()));
+    assert_eq!(v.count(), 5);
+    assert_eq!(v.total_size(), 23);
+    assert_eq!(v.item_size(1), Some(7));
+}
+
+#[test]
+fn validate_extractor_counts_violations() {
+    let opts = VirtualizerOptions::new(10, |_| 1)
+        .with_overscan(0)
+        .with_validate_extractor(true)
+        .with_range_extractor(Some(|r: Range, emit: &mut dyn FnMut(usize)| {
+            emit(r.start_index + 1);
+            emit(r.start_index); // unsorted
+            emit(r.count); // out of bounds
+            emit(r.start_index + 2);
+        }));
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(3);
+    assert_eq!(v.extractor_violations(), 0);
+
+    let mut out = Vec::new();
+    v.collect_virtual_indexes(&mut out);
+    assert_eq!(out, vec![1, 2]);
+    assert_eq!(v.extractor_violations(), 2);
+
+    v.collect_virtual_indexes(&mut out);
+    assert_eq!(v.extractor_violations(), 4);
+
+    v.reset_extractor_violations();
+    assert_eq!(v.extractor_violations(), 0);
+
+    v.collect_virtual_indexes(&mut out);
+    v.set_range_extractor(Some(|r: Range, emit: &mut dyn FnMut(usize)| {
+        IndexEmitter::new(r, emit).emit_visible();
+    }));
+    assert_eq!(v.extractor_violations(), 0);
+    v.collect_virtual_indexes(&mut out);
+    assert_eq!(out, vec![0, 1, 2]);
+    assert_eq!(v.extractor_violations(), 0);
+}
+
+#[test]
+fn scroll_to_index_offset_for_uses_explicit_viewport() {
+    let v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    assert_eq!(v.viewport_size(), 0);
+
+    // With the default zero viewport, End/Center collapse onto the item bounds.
+    assert_eq!(v.scroll_to_index_offset(50, Align::End), 510);
+    assert_eq!(v.scroll_to_index_offset(50, Align::Center), 505);
+
+    assert_eq!(v.scroll_to_index_offset_for(50, Align::Start, 100), 500);
+    assert_eq!(v.scroll_to_index_offset_for(50, Align::End, 100), 410);
+    assert_eq!(v.scroll_to_index_offset_for(50, Align::Center, 100), 455);
+
+    // Clamping uses the explicit viewport too.
+    assert_eq!(v.scroll_to_index_offset_for(99, Align::Start, 100), 900);
+}
+
+#[test]
+fn overshoot_reports_distance_outside_scroll_bounds() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.set_viewport_size(30);
+    assert_eq!(v.max_scroll_offset(), 70);
+
+    assert_eq!(v.overshoot(-15), -15);
+    assert_eq!(v.overshoot(0), 0);
+    assert_eq!(v.overshoot(35), 0);
+    assert_eq!(v.overshoot(70), 0);
+    assert_eq!(v.overshoot(95), 25);
+
+    v.apply_scroll_offset_event_clamped(95, 0);
+    assert_eq!(v.scroll_offset(), 70);
+    assert_eq!(v.last_overshoot(), 25);
+
+    v.apply_scroll_offset_event_clamped(40, 10);
+    assert_eq!(v.last_overshoot(), 0);
+
+    v.apply_scroll_offset_event(200, 20);
+    assert_eq!(v.last_overshoot(), 130);
+    v.set_is_scrolling(false);
+    assert_eq!(v.last_overshoot(), 0);
+}
+
+#[test]
+fn refresh_estimates_incremental_matches_full_rebuild_without_key_lookups() {
+    let key_calls = Arc::new(AtomicUsize::new(0));
+    let mut opts = VirtualizerOptions::new_with_key(1000, |_| 10, {
+        let key_calls = Arc::clone(&key_calls);
+        move |i| {
+            key_calls.fetch_add(1, Ordering::Relaxed);
+            i as u64
+        }
+    });
+    opts.gap = 2;
+    let mut v = Virtualizer::new(opts);
+    v.measure(3, 40);
+    v.measure(500, 7);
+
+    // Few changes: patched in place.
+    key_calls.store(0, Ordering::Relaxed);
+    v.set_estimate_size_incremental(|i| if i % 100 == 0 { 25 } else { 10 });
+    assert_eq!(key_calls.load(Ordering::Relaxed), 0);
+    assert_eq!(v.item_size(3), Some(40));
+    assert_eq!(v.item_size(100), Some(25));
+    assert_eq!(v.item_size(500), Some(7));
+
+    let mut full = v.clone();
+    full.set_estimate_size(|i| if i % 100 == 0 { 25 } else { 10 });
+    assert_eq!(v.total_size(), full.total_size());
+    assert_eq!(v.debug_positions(), full.debug_positions());
+
+    // Many changes: falls back to a single rebuild.
+    key_calls.store(0, Ordering::Relaxed);
+    v.set_estimate_size_incremental(|i| i as u32 % 7);
+    assert_eq!(key_calls.load(Ordering::Relaxed), 0);
+    full.set_estimate_size(|i| i as u32 % 7);
+    assert_eq!(v.total_size(), full.total_size());
+    assert_eq!(v.debug_positions(), full.debug_positions());
+}
+
+#[test]
+fn align_center_if_needed_only_moves_for_offscreen_items() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_size(50);
+    v.set_scroll_offset(100);
+
+    // Item 12 is [120, 130), fully visible in [100, 150).
+    assert_eq!(v.scroll_to_index_offset(12, Align::CenterIfNeeded), 100);
+
+    // Item 40 is [400, 410): Auto aligns to the end edge, CenterIfNeeded centers it.
+    assert_eq!(v.scroll_to_index_offset(40, Align::Auto), 360);
+    assert_eq!(v.scroll_to_index_offset(40, Align::CenterIfNeeded), 380);
+    assert_eq!(
+        v.scroll_to_index_offset(40, Align::CenterIfNeeded),
+        v.scroll_to_index_offset(40, Align::Center)
+    );
+
+    // Items before the viewport are centered too.
+    assert_eq!(v.scroll_to_index_offset(5, Align::Auto), 50);
+    assert_eq!(v.scroll_to_index_offset(5, Align::CenterIfNeeded), 30);
+}
+
+#[test]
+fn virtual_range_set_operations() {
+    let r = |start_index, end_index| VirtualRange {
+        start_index,
+        end_index,
+    };
+    let empty = r(0, 0);
+
+    assert_eq!(r(2, 5).len(), 3);
+    assert_eq!(empty.len(), 0);
+    assert!(r(2, 5).contains(2));
+    assert!(r(2, 5).contains(4));
+    assert!(!r(2, 5).contains(5));
+    assert!(!r(2, 5).contains(1));
+    assert!(!empty.contains(0));
+
+    // Disjoint.
+    assert!(r(0, 3).intersection(r(5, 8)).is_empty());
+    assert_eq!(r(0, 3).union_hull(r(5, 8)), r(0, 8));
+
+    // Adjacent ranges do not overlap.
+    assert!(r(0, 3).intersection(r(3, 6)).is_empty());
+
+    // Overlapping.
+    assert_eq!(r(0, 5).intersection(r(3, 8)), r(3, 5));
+    assert_eq!(r(3, 8).union_hull(r(0, 5)), r(0, 8));
+
+    // Nested.
+    assert_eq!(r(0, 10).intersection(r(3, 5)), r(3, 5));
+    assert_eq!(r(3, 5).union_hull(r(0, 10)), r(0, 10));
+
+    // Empty ranges: identity for union, absorbing for intersection.
+    assert_eq!(r(4, 7).union_hull(r(9, 9)), r(4, 7));
+    assert_eq!(r(9, 9).union_hull(r(4, 7)), r(4, 7));
+    assert!(r(4, 7).intersection(r(5, 5)).is_empty());
+}
+
+#[test]
+fn resize_item_at_offset_resolves_index() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.set_viewport_size(20);
+    v.set_scroll_offset(50);
+
+    // Offset 25 is inside item 2 ([20, 30)), which is above the scroll offset.
+    let applied = v.resize_item_at_offset(25, 22);
+    assert_eq!(applied, 12);
+    assert_eq!(v.item_size(2), Some(22));
+    assert_eq!(v.scroll_offset(), 62);
+
+    // Items after the scroll offset do not adjust it.
+    assert_eq!(v.resize_item_at_offset(75, 15), 0);
+    assert_eq!(v.item_size(6), Some(15));
+    assert_eq!(v.scroll_offset(), 62);
+
+    let mut empty = Virtualizer::new(VirtualizerOptions::new(0, |_| 10));
+    assert_eq!(empty.resize_item_at_offset(0, 5), 0);
+
+    v.set_enabled(false);
+    assert_eq!(v.resize_item_at_offset(25, 1), 0);
+}
+
+#[test]
+fn measure_reports_whether_size_changed() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(5, |_| 10));
+
+    // Matching the estimate is a no-op for layout (but still marks the item measured).
+    assert!(!v.measure(0, 10));
+    assert!(v.is_measured(0));
+
+    assert!(v.measure(1, 12));
+    assert!(!v.measure(1, 12));
+    assert!(v.measure_keyed(2, 2, 3));
+    assert!(!v.measure_keyed(2, 2, 3));
+
+    assert!(!v.measure(99, 1));
+
+    // No-op measurements don't notify.
+    let calls = Arc::new(AtomicUsize::new(0));
+    let c = Arc::clone(&calls);
+    v.set_on_change(Some(move |_: &Virtualizer<u64>, _| {
+        c.fetch_add(1, Ordering::Relaxed);
+    }));
+    let before = calls.load(Ordering::Relaxed);
+    assert!(!v.measure(1, 12));
+    v.measure_unadjusted(1, 12);
+    assert_eq!(v.resize_item(1, 12), 0);
+    assert_eq!(calls.load(Ordering::Relaxed), before);
+    assert!(v.measure(1, 13));
+    assert_eq!(calls.load(Ordering::Relaxed), before + 1);
+}
+
+#[test]
+fn rebase_window_slides_forward_and_reuses_measurements() {
+    let estimate_calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, {
+        let estimate_calls = Arc::clone(&estimate_calls);
+        move |i| {
+            estimate_calls.lock().unwrap().push(i);
+            10
+        }
+    }));
+    v.set_viewport_size(50);
+    v.measure(60, 30);
+    v.measure(10, 30);
+    v.set_scroll_offset(625); // item 60 starts at 620 (+20 from item 10)
+    assert_eq!(v.visible_range().start_index, 60);
+    assert_eq!(v.measurement_cache_len(), 2);
+
+    estimate_calls.lock().unwrap().clear();
+    v.rebase_window(50, 100);
+    assert_eq!(v.window_base(), 50);
+    assert_eq!(v.count(), 100);
+
+    // Closures see absolute indexes.
+    let calls = estimate_calls.lock().unwrap().clone();
+    assert_eq!(calls.first(), Some(&50));
+    assert_eq!(calls.last(), Some(&149));
+    assert_eq!(v.key_for(0), 50);
+    assert_eq!(v.absolute_index(10), 60);
+
+    // Absolute item 60 (local 10) keeps its measurement; item 10 left the window.
+    assert_eq!(v.item_size(10), Some(30));
+    assert!(v.is_measured(10));
+    assert_eq!(v.measurement_cache_len(), 1);
+
+    // The viewport stays anchored on absolute item 60.
+    assert_eq!(v.item_start(10), Some(100));
+    assert_eq!(v.scroll_offset(), 105);
+    assert_eq!(v.visible_range().start_index, 10);
+}
+
+#[test]
+fn leading_and_trailing_space_match_spacer_layout() {
+    let mut opts = VirtualizerOptions::new(50, |i| 5 + (i % 3) as u32);
+    opts.gap = 2;
+    opts.padding_start = 7;
+    opts.padding_end = 11;
+    opts.scroll_margin = 20;
+    opts.overscan = 2;
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(30);
+    v.set_scroll_offset(200);
+
+    let range = v.virtual_range();
+    assert!(!range.is_empty());
+
+    // Leading spacer ends exactly where the first rendered item starts (in list coordinates).
+    let first = v.item_start(range.start_index).unwrap() - 20;
+    assert_eq!(v.leading_space(), first);
+
+    // Rendered content spans first start..last end (inner gaps included).
+    let last_end = v.item_end(range.end_index - 1).unwrap() - 20;
+    assert_eq!(v.content_size_in(range), last_end - first);
+
+    // Trailing spacer covers the gap after the last rendered item plus padding_end.
+    assert_eq!(
+        v.leading_space() + v.content_size_in(range) + v.trailing_space(),
+        v.total_size()
+    );
+    assert_eq!(v.trailing_space(), v.total_size() - last_end);
+
+    // Nothing rendered: everything is trailing space.
+    v.set_scroll_offset(0);
+    v.set_viewport_size(10);
+    assert!(v.virtual_range().is_empty());
+    assert_eq!(v.leading_space(), 0);
+    assert_eq!(v.trailing_space(), v.total_size());
+}
+
+#[test]
+fn on_change_with_reason_tags_mutations() {
+    let reasons = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |_| 1).with_on_change_with_reason(Some({
+            let reasons = Arc::clone(&reasons);
+            move |_: &Virtualizer<u64>, reason: ChangeReason| {
+                reasons.lock().unwrap().push(reason);
+            }
+        })),
+    );
+    let take = || core::mem::take(&mut *reasons.lock().unwrap());
+
+    v.set_viewport_size(5);
+    assert_eq!(take(), vec![ChangeReason::Resize]);
+    v.set_scroll_offset(3);
+    assert_eq!(take(), vec![ChangeReason::Scroll]);
+    v.measure(8, 4);
+    assert_eq!(take(), vec![ChangeReason::Measure]);
+    v.set_count(20);
+    assert_eq!(take(), vec![ChangeReason::Count]);
+    v.set_overscan(3);
+    assert_eq!(take(), vec![ChangeReason::Options]);
+    v.set_is_scrolling(true);
+    assert_eq!(take(), vec![ChangeReason::ScrollingFlag]);
+
+    // Coalesced updates report the most significant reason.
+    v.apply_scroll_offset_event(4, 0);
+    assert_eq!(take(), vec![ChangeReason::Scroll]);
+    v.apply_scroll_frame(Rect { main: 6, cross: 0 }, 5, 0);
+    assert_eq!(take(), vec![ChangeReason::Resize]);
+}
+
+#[test]
+fn edge_gaps_participate_in_layout_and_scroll_to() {
+    // Every item has a symmetric margin of 2: gap 4 between, 2 at the ends.
+    let opts = VirtualizerOptions::new(5, |_| 10)
+        .with_gap(4)
+        .with_edge_gaps(2, 2)
+        .with_padding(5, 5);
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(20);
+
+    // padding(5) + leading(2) + 5*10 + 4*4 + trailing(2) + padding(5)
+    assert_eq!(v.total_size(), 80);
+    assert_eq!(v.item_start(0), Some(7));
+    assert_eq!(v.item_start(1), Some(21));
+    assert_eq!(v.item_end(4), Some(73));
+
+    // Offsets inside the leading gap map to the first item.
+    assert_eq!(v.index_at_offset(6), Some(0));
+
+    // Plain scroll-to aligns the item itself; the gap-aware variant reveals the edge gaps.
+    assert_eq!(v.scroll_to_index_offset(0, Align::Start), 7);
+    assert_eq!(v.scroll_to_index_offset_with_gap(0, Align::Start), 5);
+    assert_eq!(v.scroll_to_index_offset(4, Align::End), 53);
+    assert_eq!(v.scroll_to_index_offset_with_gap(4, Align::End), 55);
+
+    v.set_edge_gaps(0, 0);
+    assert_eq!(v.total_size(), 76);
+    assert_eq!(v.item_start(0), Some(5));
+}
+
+#[test]
+fn measure_including_gap_subtracts_gap() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(3, |_| 10).with_gap(4));
+    assert_eq!(v.item_size_with_gap(0), Some(14));
+    // No gap after the last item.
+    assert_eq!(v.item_size_with_gap(2), Some(10));
+
+    assert!(v.measure_including_gap(0, 20));
+    assert_eq!(v.item_size(0), Some(16));
+    assert_eq!(v.item_size_with_gap(0), Some(20));
+
+    // Sizes smaller than the gap clamp to zero.
+    assert!(v.measure_including_gap(1, 3));
+    assert_eq!(v.item_size(1), Some(0));
+
+    assert!(v.measure_including_gap(2, 12));
+    assert_eq!(v.item_size(2), Some(12));
+    assert_eq!(v.item_size_with_gap(99), None);
+}
+
+#[test]
+fn adjust_scroll_on_resize_false_never_moves_offset() {
+    let opts = VirtualizerOptions::new(5, |_| 10)
+        .with_adjust_scroll_on_resize(false)
+        .with_should_adjust_scroll_position_on_item_size_change(Some(
+            |_: &Virtualizer<u64>, _: VirtualItem, _: i64| true,
+        ));
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(10);
+    v.set_scroll_offset(30);
+
+    assert_eq!(v.resize_item(0, 15), 0);
+    assert_eq!(v.scroll_offset(), 30);
+    assert_eq!(v.item_size(0), Some(15));
+
+    v.set_adjust_scroll_on_resize(true);
+    assert_eq!(v.resize_item(1, 20), 10);
+    assert_eq!(v.scroll_offset(), 40);
+}
+
+#[test]
+fn hash_key_is_stable_fnv1a() {
+    use crate::{FnvKey, hash_key};
+
+    // Reference FNV-1a 64-bit values.
+    assert_eq!(hash_key(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash_key(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(hash_key(b"foobar"), 0x8594_4171_f739_67e8);
+
+    let ids = [
+        "row-0", "row-1", "row-2", "row-10", "row-01", "a", "b", "ab", "ba", "",
+    ];
+    let mut hashes: Vec<u64> = ids.iter().map(|s| hash_key(s.as_bytes())).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    assert_eq!(hashes.len(), ids.len());
+
+    let k = FnvKey::from("row-1");
+    assert_eq!(u64::from(k), hash_key(b"row-1"));
+    assert_eq!(k, FnvKey::from_bytes(b"row-1"));
+}
+
+#[test]
+fn scroll_velocity_from_last_two_events() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_size(50);
+    assert_eq!(v.scroll_velocity(), 0.0);
+
+    v.apply_scroll_offset_event(100, 1000);
+    assert_eq!(v.scroll_velocity(), 0.0);
+    v.apply_scroll_offset_event(110, 1010);
+    assert_eq!(v.scroll_velocity(), 1.0);
+
+    v.apply_scroll_offset_event(90, 1020);
+    assert_eq!(v.scroll_velocity(), -2.0);
+
+    v.update_scrolling(1020 + v.options().is_scrolling_reset_delay_ms);
+    assert!(!v.is_scrolling());
+    assert_eq!(v.scroll_velocity(), 0.0);
+}
+
+#[test]
+fn freeze_measured_ignores_second_measurement() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10).with_freeze_measured(true));
+    v.set_viewport_size(30);
+
+    assert!(v.measure(2, 25));
+    assert_eq!(v.item_size(2), Some(25));
+
+    assert!(!v.measure(2, 40));
+    assert_eq!(v.resize_item(2, 40), 0);
+    assert_eq!(v.item_size(2), Some(25));
+    assert_eq!(v.total_size(), 9 * 10 + 25);
+
+    // Unmeasured items are still measured once.
+    assert!(v.measure(3, 5));
+    assert_eq!(v.item_size(3), Some(5));
+
+    v.set_freeze_measured(false);
+    assert!(v.measure(2, 40));
+    assert_eq!(v.item_size(2), Some(40));
+}
+
+#[test]
+fn for_each_virtual_run_coalesces_pinned_islands() {
+    let pinned: Arc<[usize]> = Arc::from([0usize, 10, 20, 30, 40, 999]);
+
+    let mut opts = VirtualizerOptions::new(1_000, |_| 1);
+    opts.overscan = 2;
+    opts.range_extractor = Some(Arc::new({
+        let pinned = Arc::clone(&pinned);
+        move |r: Range, emit: &mut dyn FnMut(usize)| {
+            let mut e = IndexEmitter::new(r, emit);
+            let overscanned_start = r.start_index.saturating_sub(r.overscan);
+            let overscanned_end = r.end_index.saturating_add(r.overscan).min(r.count);
+            for &idx in pinned.iter().filter(|&&i| i < overscanned_start) {
+                e.emit_pinned(idx);
+            }
+            e.emit_overscanned();
+            for &idx in pinned.iter().filter(|&&i| i >= overscanned_end) {
+                e.emit_pinned(idx);
+            }
+        }
+    }));
+
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_and_scroll_clamped(10, 500);
+
+    let mut runs = Vec::new();
+    v.for_each_virtual_run(|r| runs.push((r.start_index, r.end_index)));
+    assert_eq!(
+        runs,
+        vec![
+            (0, 1),
+            (10, 11),
+            (20, 21),
+            (30, 31),
+            (40, 41),
+            (498, 512),
+            (999, 1000)
+        ]
+    );
+
+    // Without an extractor: a single run equal to the overscanned range.
+    v.set_range_extractor(None::<fn(Range, &mut dyn FnMut(usize))>);
+    let mut runs = Vec::new();
+    v.for_each_virtual_run(|r| runs.push(r));
+    assert_eq!(runs, vec![v.virtual_range()]);
+}
+
+#[test]
+fn prefetch_range_follows_scroll_direction() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10).with_overscan(2));
+    v.set_viewport_size(50);
+    assert!(v.prefetch_range(5).is_empty());
+
+    v.apply_scroll_offset_event(200, 0);
+    assert_eq!(
+        v.virtual_range(),
+        VirtualRange {
+            start_index: 18,
+            end_index: 27
+        }
+    );
+    assert_eq!(
+        v.prefetch_range(5),
+        VirtualRange {
+            start_index: 27,
+            end_index: 32
+        }
+    );
+
+    v.apply_scroll_offset_event(100, 16);
+    assert_eq!(
+        v.virtual_range(),
+        VirtualRange {
+            start_index: 8,
+            end_index: 17
+        }
+    );
+    assert_eq!(
+        v.prefetch_range(5),
+        VirtualRange {
+            start_index: 3,
+            end_index: 8
+        }
+    );
+    assert_eq!(
+        v.prefetch_range(50),
+        VirtualRange {
+            start_index: 0,
+            end_index: 8
+        }
+    );
+
+    // Clamped at the end.
+    v.apply_scroll_offset_event(950, 32);
+    assert_eq!(v.virtual_range().end_index, 100);
+    assert!(v.prefetch_range(5).is_empty());
+}
+
+#[test]
+fn all_zero_sizes_are_safe() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(50, |_| 0));
+    v.set_viewport_size(20);
+    assert_eq!(v.total_size(), 0);
+    assert_eq!(v.max_scroll_offset(), 0);
+
+    for off in [0u64, 1, 10, u64::MAX] {
+        v.set_scroll_offset(off);
+        let r = v.virtual_range();
+        assert!(r.start_index <= r.end_index && r.end_index <= 50);
+        assert!(v.visible_range().is_empty());
+        assert!(v.index_at_offset(off).is_some_and(|i| i < 50));
+    }
+    v.for_each_virtual_item(|it| assert!(it.index < 50));
+}
+
+#[test]
+fn property_zero_sized_items_never_panic() {
+    for seed in [7u64, 11, 42, 77, 1000, 31337] {
+        let mut rng = Lcg::new(seed);
+
+        let count = rng.gen_range_usize(1, 64);
+        let sizes: Vec<u32> = (0..count)
+            .map(|_| {
+                if rng.gen_bool() {
+                    0
+                } else {
+                    rng.gen_range_u32(1, 8)
+                }
+            })
+            .collect();
+
+        let mut opts = VirtualizerOptions::from_sizes(sizes.clone());
+        opts.gap = rng.gen_range_u32(0, 3);
+        opts.overscan = rng.gen_range_usize(0, 3);
+        opts.padding_start = rng.gen_range_u32(0, 4);
+        let mut v = Virtualizer::new(opts);
+
+        let total = v.total_size();
+        for _ in 0..64 {
+            v.set_viewport_size(rng.gen_range_u32(0, 16));
+            let off = rng.gen_range_u64(0, total + 8);
+            v.set_scroll_offset(off);
+
+            let r = v.virtual_range();
+            assert!(r.start_index <= r.end_index && r.end_index <= count);
+            let vis = v.visible_range();
+            assert!(vis.start_index <= vis.end_index && vis.end_index <= count);
+            assert!(v.index_at_offset(off).is_some_and(|i| i < count));
+
+            // Randomly flip sizes between zero and non-zero.
+            let i = rng.gen_range_usize(0, count);
+            let size = if rng.gen_bool() {
+                0
+            } else {
+                rng.gen_range_u32(1, 8)
+            };
+            v.resize_item(i, size);
+        }
+
+        v.for_each_virtual_item(|it| assert!(it.index < count));
+    }
+}
+
+#[test]
+fn scroll_to_index_offset_within_aligns_point_in_item() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(
+        10,
+        |i| if i == 3 { 1_000 } else { 10 },
+    ));
+    v.set_viewport_size(100);
+
+    // Item 3 starts at 30.
+    assert_eq!(
+        v.scroll_to_index_offset_within(3, 400, Align::Center),
+        30 + 400 - 50
+    );
+    assert_eq!(v.scroll_to_index_offset_within(3, 400, Align::Start), 430);
+    assert_eq!(v.scroll_to_index_offset_within(3, 400, Align::End), 330);
+
+    // Clamped to the item size.
+    assert_eq!(
+        v.scroll_to_index_offset_within(3, 5_000, Align::Center),
+        v.scroll_to_index_offset_within(3, 1_000, Align::Center)
+    );
+    assert_eq!(
+        v.scroll_to_index_offset_within(3, 5_000, Align::Center),
+        980
+    );
+
+    // Auto keeps the current offset if the point is already visible.
+    v.set_scroll_offset(400);
+    assert_eq!(v.scroll_to_index_offset_within(3, 400, Align::Auto), 400);
+}
+
+#[test]
+fn item_size_bounds_clamp_estimates_and_measurements() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |i| if i == 0 { 0 } else { 10 })
+            .with_item_size_bounds(4, Some(100)),
+    );
+    v.set_viewport_size(50);
+
+    // Estimates are clamped.
+    assert_eq!(v.item_size(0), Some(4));
+
+    // Measurements are clamped.
+    v.measure(1, 0);
+    assert_eq!(v.item_size(1), Some(4));
+    v.measure(2, 1 << 31);
+    assert_eq!(v.item_size(2), Some(100));
+    v.measure_many_unadjusted([(3, 0), (4, u32::MAX)]);
+    assert_eq!(v.item_size(3), Some(4));
+    assert_eq!(v.item_size(4), Some(100));
+    assert_eq!(v.total_size(), 4 + 4 + 100 + 4 + 100 + 5 * 10);
+
+    // Changing the bounds re-applies them (cached measurements included).
+    v.set_item_size_bounds(0, Some(50));
+    assert_eq!(v.item_size(0), Some(0));
+    assert_eq!(v.item_size(2), Some(50));
+    assert_eq!(v.item_size(3), Some(4));
+}
+
+#[test]
+fn for_each_virtual_item_rev_matches_forward_pass() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |i| 5 + (i % 7) as u32)
+            .with_overscan(3)
+            .with_gap(2)
+            .with_scroll_margin(4)
+            .with_padding(6, 6),
+    );
+    v.set_viewport_and_scroll_clamped(40, 300);
+
+    let mut fwd = Vec::new();
+    v.for_each_virtual_item(|it| fwd.push(it));
+    let mut rev = Vec::new();
+    v.for_each_virtual_item_rev(|it| rev.push(it));
+    assert!(!fwd.is_empty());
+    rev.reverse();
+    assert_eq!(fwd, rev);
+
+    // Extractor path.
+    v.set_range_extractor(Some(|r: Range, emit: &mut dyn FnMut(usize)| {
+        let mut e = IndexEmitter::new(r, emit);
+        e.emit_pinned(0);
+        e.emit_overscanned();
+    }));
+    let mut fwd = Vec::new();
+    v.for_each_virtual_item(|it| fwd.push(it));
+    let mut rev = Vec::new();
+    v.for_each_virtual_item_rev(|it| rev.push(it));
+    assert_eq!(fwd[0].index, 0);
+    rev.reverse();
+    assert_eq!(fwd, rev);
+}
+
+#[test]
+fn masonry_places_greedily_and_reflows_on_measure() {
+    let heights = [10u32, 30, 20, 10, 10, 40];
+    let mut m = MasonryVirtualizer::new(heights.len(), 3, move |i| {
+        heights.get(i).copied().unwrap_or(20)
+    })
+    .with_gap(2);
+    m.set_scroll_rect(Rect {
+        main: 15,
+        cross: 304,
+    });
+    assert_eq!(m.column_width(), 100);
+
+    let placed: Vec<(usize, u64)> = (0..6)
+        .map(|i| {
+            let it = m.item(i).unwrap();
+            (it.column, it.y)
+        })
+        .collect();
+    // 0,1,2 fill the first row; 3 -> col 0 (end 12), 4 -> col 0 (end 24), 5 -> col 2 (end 22).
+    assert_eq!(
+        placed,
+        vec![(0, 0), (1, 0), (2, 0), (0, 12), (2, 22), (0, 24)]
+    );
+    assert_eq!(m.item(2).unwrap().x, 204);
+    assert_eq!(m.total_size(), 64);
+
+    // Viewport [20, 35) intersects items 3 and 5 (col 0), 1 (col 1) and 4 (col 2).
+    m.set_scroll_offset(20);
+    let mut vis = Vec::new();
+    m.clone()
+        .with_overscan(0)
+        .for_each_virtual_item(|it| vis.push(it.index));
+    vis.sort_unstable();
+    assert_eq!(vis, vec![1, 3, 4, 5]);
+
+    // Growing item 0 reflows the rest of the layout.
+    assert!(m.measure(0, 50));
+    assert!(!m.measure(0, 50));
+    let placed: Vec<(usize, u64)> = (0..6)
+        .map(|i| {
+            let it = m.item(i).unwrap();
+            (it.column, it.y)
+        })
+        .collect();
+    // 3 -> col 2 (end 22), 4 -> col 1 (end 32), 5 -> col 2 (end 34).
+    assert_eq!(
+        placed,
+        vec![(0, 0), (1, 0), (2, 0), (2, 22), (1, 32), (2, 34)]
+    );
+    assert_eq!(m.total_size(), 74);
+
+    // Count changes keep the existing placement.
+    m.set_count(7);
+    assert_eq!(m.item(6).unwrap().column, 1);
+    assert_eq!(m.item(6).unwrap().y, 44);
+    m.set_count(4);
+    assert!(m.item(4).is_none());
+    assert_eq!(m.total_size(), 50);
+}
+
+#[test]
+fn set_options_reports_what_was_rebuilt() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.set_viewport_size(30);
+
+    let change = v.update_options(|o| o.overscan = 5);
+    assert_eq!(change, OptionsChange::default());
+
+    let change = v.update_options(|o| o.count = 20);
+    assert!(change.count_changed && change.rebuilt_estimates);
+    assert!(!change.rebuilt_fenwick && !change.reset);
+
+    let change = v.update_options(|o| o.gap = 2);
+    assert!(change.rebuilt_fenwick && !change.rebuilt_estimates && !change.count_changed);
+
+    let change = v.update_options(|o| o.estimate_size = Arc::new(|_| 7));
+    assert!(change.rebuilt_estimates && !change.count_changed);
+
+    let change = v.update_options(|o| o.enabled = false);
+    assert!(change.reset);
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+fn fixed_virtualizer_matches_dynamic_queries() {
+    let sizes: Vec<u32> = (0..40).map(|i| 3 + (i % 5) as u32).collect();
+    let mut fixed = VirtualizerFixed::<64>::from_sizes(&sizes)
+        .with_gap(1)
+        .with_padding(2, 3)
+        .with_overscan(2);
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::from_sizes(sizes.clone())
+            .with_gap(1)
+            .with_padding(2, 3)
+            .with_overscan(2),
+    );
+    assert_eq!(fixed.capacity(), 64);
+
+    for (viewport, offset) in [(10u32, 0u64), (17, 33), (25, 120), (8, 10_000)] {
+        fixed.set_viewport_size(viewport);
+        fixed.set_scroll_offset_clamped(offset);
+        v.set_viewport_and_scroll_clamped(viewport, offset);
+
+        assert_eq!(fixed.total_size(), v.total_size());
+        assert_eq!(fixed.scroll_offset(), v.scroll_offset());
+        assert_eq!(fixed.virtual_range(), v.virtual_range());
+        assert_eq!(fixed.index_at_offset(offset), v.index_at_offset(offset));
+        for align in [Align::Start, Align::Center, Align::End, Align::Auto] {
+            assert_eq!(
+                fixed.scroll_to_index_offset(20, align),
+                v.scroll_to_index_offset(20, align)
+            );
+        }
+
+        let mut a = Vec::new();
+        fixed.for_each_virtual_item(|it| a.push(it));
+        let mut b = Vec::new();
+        v.for_each_virtual_item(|it| b.push(it));
+        assert_eq!(a, b);
+    }
+
+    assert!(fixed.measure(5, 30));
+    v.measure(5, 30);
+    assert_eq!(fixed.item_start(6), v.item_start(6));
+    assert_eq!(fixed.total_size(), v.total_size());
+
+    // Count is capped at the capacity.
+    fixed.set_count(100, |_| 1);
+    assert_eq!(fixed.count(), 64);
+    assert_eq!(fixed.item_size(63), Some(1));
+    assert!(!fixed.is_measured(63));
+}
+
+#[test]
+fn items_before_offset_boundary_semantics() {
+    // Items of size 10 with a gap of 4: item i spans [5 + 14 * i, 5 + 14 * i + 10).
+    let v = Virtualizer::new(
+        VirtualizerOptions::new(10, |_| 10)
+            .with_gap(4)
+            .with_scroll_margin(2)
+            .with_padding(3, 0),
+    );
+    let start5 = v.item_start(5).unwrap();
+    assert_eq!(start5, 5 + 14 * 5);
+
+    assert_eq!(v.items_before_offset(0), 0);
+    assert_eq!(v.items_before_offset(5), 0);
+    assert_eq!(v.items_before_offset(start5), 5);
+    assert_eq!(v.index_at_offset(start5), Some(5));
+    assert_eq!(v.items_before_offset(start5 + 9), 5);
+    // At item 5's end and inside the following gap.
+    assert_eq!(v.items_before_offset(start5 + 10), 6);
+    assert_eq!(v.items_before_offset(start5 + 13), 6);
+    assert_eq!(v.index_at_offset(start5 + 13), Some(5));
+    assert_eq!(v.items_before_offset(start5 + 14), 6);
+    // Past the end.
+    assert_eq!(v.items_before_offset(u64::MAX), 10);
+}
+
+#[test]
+fn negative_scroll_margin_starts_list_before_origin() {
+    let mut opts = VirtualizerOptions::new(100, |_| 10);
+    opts.scroll_margin = -25;
+    opts.overscan = 0;
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(30);
+
+    // Item 2 spans [20, 30) in list space, i.e. [-5, 5) in the container.
+    assert_eq!(v.item_start(0), Some(0));
+    assert_eq!(v.item_start(2), Some(0));
+    assert_eq!(v.item_start(3), Some(5));
+    assert_eq!(v.scroll_offset_in_list(), 25);
+
+    assert_eq!(v.index_at_offset(0), Some(2));
+    assert_eq!(v.index_at_offset(5), Some(3));
+    assert_eq!(
+        v.visible_range(),
+        VirtualRange {
+            start_index: 2,
+            end_index: 6
+        }
+    );
+    assert_eq!(v.items_before_offset(0), 2);
+
+    let mut starts = Vec::new();
+    v.for_each_virtual_item(|it| starts.push((it.index, it.start)));
+    assert_eq!(starts, vec![(2, 0), (3, 5), (4, 15), (5, 25)]);
+
+    // The list is 1000 tall, so it ends at 975 in the container.
+    assert_eq!(v.max_scroll_offset(), 975 - 30);
+    assert_eq!(v.scroll_to_index_offset(99, Align::End), 945);
+    assert_eq!(v.scroll_to_index_offset(10, Align::Start), 75);
+}
+
+#[test]
+fn shrink_to_fit_releases_buffer_capacity() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10_000, |_| 1));
+    assert!(v.capacity() >= 10_000);
+
+    v.set_count(100);
+    assert!(v.capacity() >= 10_000);
+
+    v.shrink_to(500);
+    assert!(v.capacity() >= 500 && v.capacity() < 10_000);
+
+    v.shrink_to_fit();
+    assert!(v.capacity() >= 100 && v.capacity() < 500);
+    assert_eq!(v.total_size(), 100);
+
+    v.reserve(1_000);
+    assert!(v.capacity() >= 1_100);
+    v.set_count(1_100);
+    assert_eq!(v.total_size(), 1_100);
+}
+
+#[test]
+fn align_end_last_item_with_margin_and_paddings() {
+    // 10 items of 10, padding 5/20, scroll_margin 10: the list occupies [10, 135).
+    let mut opts = VirtualizerOptions::new(10, |_| 10)
+        .with_padding(5, 20)
+        .with_scroll_margin(10);
+    opts.scroll_padding_end = 8;
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(30);
+
+    assert_eq!(v.max_scroll_offset(), 135 - 30);
+    let last_end = v.item_end(9).unwrap();
+    assert_eq!(last_end, 115);
+
+    // The last item's end sits `scroll_padding_end` above the viewport bottom.
+    let off = v.scroll_to_index_offset(9, Align::End);
+    assert_eq!(off, 93);
+    assert_eq!(off + 30 - 8, last_end);
+
+    // A larger scroll padding is clamped to the true end of the content.
+    v.set_scroll_padding(0, 50);
+    assert_eq!(v.scroll_to_index_offset(9, Align::End), 105);
+    v.set_scroll_padding(0, 0);
+    assert_eq!(v.scroll_to_index_offset(9, Align::End), 85);
+
+    // Content shorter than the viewport: nothing to scroll, even with a margin.
+    v.set_viewport_size(200);
+    assert_eq!(v.max_scroll_offset(), 0);
+    assert_eq!(v.scroll_to_index_offset(9, Align::End), 0);
+}
+
+#[test]
+fn apply_measurements_skips_notify_on_noop_frame() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10).with_on_change(Some({
+        let calls = Arc::clone(&calls);
+        move |_: &Virtualizer<u64>, _| {
+            calls.fetch_add(1, Ordering::Relaxed);
+        }
+    })));
+    v.set_viewport_size(30);
+    let base = calls.load(Ordering::Relaxed);
+
+    // All sizes unchanged: no notification, but items are marked measured.
+    assert!(!v.apply_measurements(&[(0, 10), (1, 10), (2, 10)]));
+    assert_eq!(calls.load(Ordering::Relaxed), base);
+    assert!(v.is_measured(1));
+    v.measure_many([(0, 10), (1, 10)]);
+    v.measure_many_unadjusted([(2, 10)]);
+    assert_eq!(calls.load(Ordering::Relaxed), base);
+
+    // A single change notifies exactly once.
+    assert!(v.apply_measurements(&[(0, 10), (1, 25), (2, 10), (99, 5)]));
+    assert_eq!(calls.load(Ordering::Relaxed), base + 1);
+    assert_eq!(v.item_size(1), Some(25));
+}
+
+#[test]
+fn is_fully_visible_and_can_scroll() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(5, |_| 10).with_padding(2, 3));
+    v.set_viewport_size(55);
+    assert!(v.is_fully_visible());
+    assert!(!v.can_scroll());
+
+    v.set_viewport_size(54);
+    assert!(!v.is_fully_visible());
+    assert!(v.can_scroll());
+
+    // The margin counts towards the scrollable content.
+    v.set_viewport_size(55);
+    v.set_scroll_margin(1);
+    assert!(v.can_scroll());
+
+    v.set_count(50);
+    v.set_viewport_size(100);
+    assert!(v.can_scroll());
+    v.set_count(0);
+    assert!(v.is_fully_visible());
+}
+
+#[test]
+fn hit_test_resolves_sticky_header_overlay() {
+    // Group headers every 10 items.
+    let is_header = |i: usize| i.is_multiple_of(10);
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10)
+            .with_overscan(1)
+            .with_range_extractor(Some(move |r: Range, emit: &mut dyn FnMut(usize)| {
+                let mut e = IndexEmitter::new(r, emit);
+                let header = r.start_index - r.start_index % 10;
+                if header < r.start_index.saturating_sub(r.overscan) {
+                    e.emit_pinned(header);
+                }
+                e.emit_overscanned();
+            })),
+    );
+    v.set_viewport_and_scroll_clamped(50, 255);
+
+    let sticky = v.active_sticky_item(is_header).unwrap();
+    assert_eq!(sticky.index, 20);
+    assert_eq!((sticky.start, sticky.size), (255, 10));
+
+    // Natural layout says item 25; the overlay says header 20.
+    assert_eq!(v.index_at_offset(258), Some(25));
+    assert_eq!(v.hit_test(258, is_header), Some(20));
+    // Below the overlay, hit-testing follows the natural layout.
+    assert_eq!(v.hit_test(265, is_header), Some(26));
+
+    // Without sticky items the hit test matches index_at_offset.
+    assert_eq!(v.hit_test(258, |_| false), Some(25));
+}
+
+#[test]
+fn debug_report_matches_accessors() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10).with_overscan(2));
+    v.set_viewport_size(50);
+    v.apply_scroll_offset_event(120, 0);
+    v.measure(3, 12);
+    v.measure(4, 10);
+
+    let r = v.debug_report();
+    assert_eq!(r.count, v.count());
+    assert!(r.enabled);
+    assert_eq!(r.viewport_size, v.viewport_size());
+    assert_eq!(r.scroll_offset, v.scroll_offset());
+    assert_eq!(r.max_scroll_offset, v.max_scroll_offset());
+    assert_eq!(r.total_size, v.total_size());
+    assert_eq!(r.visible_range, v.visible_range());
+    assert_eq!(r.virtual_range, v.virtual_range());
+    assert_eq!(r.measured_count, 2);
+    assert_eq!(r.scroll_direction, v.scroll_direction());
+
+    let text = std::format!("{r}");
+    assert!(text.contains("count: 100 (enabled: true)"));
+    assert!(text.contains("measured: 2/100"));
+}
+
+#[test]
+fn overscan_items_by_distance_orders_symmetric_overscan() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10).with_overscan(3));
+    v.set_viewport_size(50);
+    v.set_scroll_offset(200);
+    assert_eq!(v.visible_range().start_index, 20);
+    assert_eq!(v.visible_range().end_index, 25);
+
+    let mut out = Vec::new();
+    v.overscan_items_by_distance(|i, d| out.push((i, d)));
+    assert_eq!(
+        out,
+        vec![(19, 0), (25, 0), (18, 10), (26, 10), (17, 20), (27, 20)]
+    );
+
+    // Clamped at the list start: only the trailing side remains.
+    v.set_scroll_offset(0);
+    out.clear();
+    v.overscan_items_by_distance(|i, d| out.push((i, d)));
+    assert_eq!(out, vec![(5, 0), (6, 10), (7, 20)]);
+}
+
+#[test]
+fn maintained_key_index_tracks_reorder_and_count_changes() {
+    use std::sync::Mutex;
+
+    let keys = Arc::new(Mutex::new(vec![10u64, 11, 12, 13]));
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new_with_key(4, |_| 10, {
+            let keys = Arc::clone(&keys);
+            move |i| keys.lock().unwrap()[i]
+        })
+        .with_maintain_key_index(true),
+    );
+    v.set_viewport_size(20);
+    assert_eq!(v.index_for_key(&12), Some(2));
+    assert_eq!(v.scroll_to_key_offset(&12, Align::Start), Some(20));
+
+    *keys.lock().unwrap() = vec![13, 12, 11, 10];
+    v.sync_item_keys();
+    assert_eq!(v.index_for_key(&12), Some(1));
+    assert_eq!(v.index_for_key(&10), Some(3));
+
+    keys.lock().unwrap().push(14);
+    v.set_count(5);
+    assert_eq!(v.index_for_key(&14), Some(4));
+
+    keys.lock().unwrap().truncate(2);
+    v.set_count(2);
+    assert_eq!(v.index_for_key(&11), None);
+    assert_eq!(v.index_for_key(&13), Some(0));
+
+    v.set_maintain_key_index(false);
+    assert_eq!(v.index_for_key(&13), None);
+    assert_eq!(v.scroll_to_key_offset(&13, Align::Start), None);
+}
+
+#[test]
+fn scroll_to_range_offset_reveals_span_or_falls_back_to_start() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_size(50);
+
+    // Span 20..=22 is [200, 230): fits in the viewport.
+    assert_eq!(v.scroll_to_range_offset(20, 22, Align::Start), 200);
+    assert_eq!(v.scroll_to_range_offset(20, 22, Align::End), 180);
+    assert_eq!(v.scroll_to_range_offset(20, 22, Align::Center), 190);
+    // Auto scrolls minimally (span is below the viewport at offset 0).
+    assert_eq!(v.scroll_to_range_offset(22, 20, Align::Auto), 180);
+    v.set_scroll_offset(185);
+    assert_eq!(v.scroll_to_range_offset(20, 22, Align::Auto), 185);
+
+    // Span 20..=29 is 100px: does not fit, so item 20 is aligned at the start.
+    assert_eq!(v.scroll_to_range_offset(20, 29, Align::End), 200);
+    assert_eq!(v.scroll_to_range_offset(20, 29, Align::Center), 200);
+}
+
+#[test]
+fn item_bounds_and_virtual_item_match_individual_accessors() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |i| 5 + i as u32)
+            .with_gap(2)
+            .with_padding(3, 4)
+            .with_scroll_margin(7),
+    );
+    v.measure(4, 30);
+
+    for i in 0..10 {
+        let (start, end) = v.item_bounds(i).unwrap();
+        assert_eq!(Some(start), v.item_start(i));
+        assert_eq!(Some(end), v.item_end(i));
+        assert_eq!(end - start, v.item_size(i).unwrap() as u64);
+
+        let item = v.virtual_item(i).unwrap();
+        assert_eq!(item.index, i);
+        assert_eq!((item.start, item.end()), (start, end));
+    }
+    assert_eq!(v.item_bounds(10), None);
+    assert_eq!(v.virtual_item(10), None);
+
+    v.set_scroll_margin(-2);
+    assert_eq!(v.item_bounds(0), Some((1, 6)));
+
+    v.set_enabled(false);
+    assert_eq!(v.item_bounds(0), None);
+    assert_eq!(v.virtual_item(0), None);
+}
+
+#[test]
+fn center_alignment_rounds_half_pixels_per_policy() {
+    // Item `i` starts at `100 * i`; sizes vary per test case via the closure.
+    let center = |size: u32, viewport: u32, rounding: Rounding| {
+        let mut v = Virtualizer::new(
+            VirtualizerOptions::new(3, move |i| if i == 1 { size } else { 100 })
+                .with_center_rounding(rounding),
+        );
+        v.set_viewport_size(viewport);
+        v.scroll_to_index_offset(1, Align::Center)
+    };
+
+    // Even difference: exact.
+    assert_eq!(center(11, 11, Rounding::HalfUp), 100);
+    assert_eq!(center(10, 10, Rounding::HalfUp), 100);
+    assert_eq!(center(10, 20, Rounding::HalfUp), 95);
+    assert_eq!(center(11, 21, Rounding::HalfDown), 95);
+    // Odd difference: the ideal offset is on a half pixel.
+    assert_eq!(center(11, 10, Rounding::HalfUp), 101);
+    assert_eq!(center(11, 10, Rounding::HalfDown), 100);
+    assert_eq!(center(10, 11, Rounding::HalfUp), 100);
+    assert_eq!(center(10, 11, Rounding::HalfDown), 99);
+    assert_eq!(center(11, 20, Rounding::HalfUp), 96);
+    assert_eq!(center(11, 20, Rounding::HalfDown), 95);
+
+    assert_eq!(
+        VirtualizerOptions::new(1, |_| 1).center_rounding,
+        Rounding::HalfUp
+    );
+}
+
+#[test]
+fn with_capacity_growth_does_not_reallocate() {
+    let mut v = Virtualizer::with_capacity(VirtualizerOptions::new(1_000, |_| 10), 100_000);
+    assert_eq!(v.count(), 1_000);
+    let capacity = v.capacity();
+    assert!(capacity >= 100_000);
+
+    for count in (2_000..=100_000).step_by(7_000) {
+        v.set_count(count);
+        assert_eq!(v.capacity(), capacity);
+    }
+    v.set_count(100_000);
+    assert_eq!(v.capacity(), capacity);
+    assert_eq!(v.total_size(), 1_000_000);
+
+    // Full rebuilds keep the reservation too.
+    v.set_gap(1);
+    v.set_count(50_000);
+    v.set_count(100_000);
+    assert_eq!(v.capacity(), capacity);
+}
+
+#[test]
+fn center_offset_centers_arbitrary_content_offsets() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(20, |_| 10));
+    v.set_viewport_size(20);
+
+    assert_eq!(v.center_offset(100), 90);
+    // Clamped at both ends.
+    assert_eq!(v.center_offset(5), 0);
+    assert_eq!(v.center_offset(195), v.max_scroll_offset());
+
+    v.set_scroll_margin(30);
+    assert_eq!(v.center_offset(100), 120);
+
+    assert_eq!(v.scroll_to_center_offset(100), 120);
+    assert_eq!(v.scroll_offset(), 120);
+    assert!(!v.is_scrolling());
+}
+
+#[test]
+fn reset_get_item_key_restores_identity_keys() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(4, |_| 1));
+    v.measure(1, 10);
+    v.set_get_item_key(|i| 3 - i as u64);
+    assert_eq!(v.key_for(0), 3);
+    // Key 1 now lives at index 2.
+    assert_eq!(v.item_size(2), Some(10));
+
+    v.reset_get_item_key();
+    for i in 0..4 {
+        assert_eq!(v.key_for(i), i as u64);
+    }
+    assert_eq!(v.item_size(1), Some(10));
+    assert_eq!(v.item_size(2), Some(1));
+}
+
+#[test]
+fn uniform_window_until_a_measurement_breaks_uniformity() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10)
+            .with_gap(2)
+            .with_padding(5, 0)
+            .with_overscan(1),
+    );
+    v.set_viewport_size(36);
+    v.set_scroll_offset(120);
+
+    let w = v.uniform_window().unwrap();
+    let range = v.virtual_range();
+    assert_eq!(
+        (w.start_index, w.end_index),
+        (range.start_index, range.end_index)
+    );
+    assert_eq!((w.item_size, w.gap), (10, 2));
+    let mut items = Vec::new();
+    v.for_each_virtual_item(|it| items.push(it));
+    for it in &items {
+        assert_eq!(w.item_start(it.index), it.start);
+    }
+
+    // Measuring at the same size keeps the layout uniform.
+    v.measure(3, 10);
+    assert!(v.uniform_window().is_some());
+
+    v.measure(50, 11);
+    assert_eq!(v.uniform_window(), None);
+    v.measure(50, 10);
+    assert!(v.uniform_window().is_some());
+
+    v.set_gap_after(Some(|_| 1));
+    assert_eq!(v.uniform_window(), None);
+}
+
+#[test]
+fn scroll_progress_and_thumb_fraction() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.set_viewport_size(25);
+    assert_eq!(v.scroll_progress(), 0.0);
+    assert_eq!(v.thumb_size_fraction(), 0.25);
+
+    v.set_scroll_offset(v.max_scroll_offset());
+    assert_eq!(v.scroll_progress(), 1.0);
+    v.set_scroll_offset(1_000);
+    assert_eq!(v.scroll_progress(), 1.0);
+    v.set_scroll_offset(25);
+    assert_eq!(v.scroll_progress(), 1.0 / 3.0);
+
+    // Content fits in the viewport.
+    v.set_count(2);
+    v.set_scroll_offset(0);
+    assert_eq!(v.scroll_progress(), 0.0);
+    assert_eq!(v.thumb_size_fraction(), 1.0);
+    v.set_count(0);
+    assert_eq!(v.scroll_progress(), 0.0);
+    assert_eq!(v.thumb_size_fraction(), 1.0);
+}
+
+#[test]
+fn clamp_scroll_after_measure_recovers_from_shrink_past_end() {
+    let run = |clamp: bool| {
+        let mut v = Virtualizer::new(
+            VirtualizerOptions::new(10, |_| 10).with_clamp_scroll_after_measure(clamp),
+        );
+        v.set_viewport_size(30);
+        v.set_scroll_offset(70);
+        // Item 9 starts inside the viewport, so no scroll adjustment applies.
+        assert!(v.measure(9, 1));
+        let after_measure = v.scroll_offset();
+        v.measure_many([(8, 1), (7, 1)]);
+        (after_measure, v.scroll_offset(), v.max_scroll_offset())
+    };
+
+    assert_eq!(run(false), (70, 70, 43));
+    assert_eq!(run(true), (61, 43, 43));
+}
+
+#[test]
+fn align_nearest_minimizes_scroll_and_differs_from_auto_for_tall_items() {
+    // Items: 0..10 are 10px, item 10 is 100px (over-tall), then 10px items.
+    let mut v = Virtualizer::new(VirtualizerOptions::new(
+        20,
+        |i| if i == 10 { 100 } else { 10 },
+    ));
+    v.set_viewport_size(30);
+
+    // Fitting items: same as Auto.
+    for cur in [0, 25, 60, 150] {
+        v.set_scroll_offset(cur);
+        for i in [2, 5, 8, 12] {
+            assert_eq!(
+                v.scroll_to_index_offset(i, Align::Nearest),
+                v.scroll_to_index_offset(i, Align::Auto),
+                "cur={cur} i={i}"
+            );
+        }
+    }
+    v.set_scroll_offset(25);
+    assert_eq!(v.scroll_to_index_offset(3, Align::Nearest), 25);
+
+    // Over-tall item [100, 200) below the viewport: Auto reveals its end, Nearest its start.
+    v.set_scroll_offset(0);
+    assert_eq!(v.scroll_to_index_offset(10, Align::Auto), 170);
+    assert_eq!(v.scroll_to_index_offset(10, Align::Nearest), 100);
+
+    // Already filling the viewport: Nearest stays put, Auto jumps to the start.
+    v.set_scroll_offset(140);
+    assert_eq!(v.scroll_to_index_offset(10, Align::Nearest), 140);
+    assert_eq!(v.scroll_to_index_offset(10, Align::Auto), 100);
+
+    // Scroll padding is respected.
+    v.set_scroll_padding(5, 5);
+    v.set_scroll_offset(0);
+    assert_eq!(v.scroll_to_index_offset(3, Align::Nearest), 15);
+    v.set_scroll_offset(60);
+    assert_eq!(v.scroll_to_index_offset(3, Align::Nearest), 25);
+}
+
+#[test]
+fn property_edge_case_invariants_hold_without_panics() {
+    // Exercises zero sizes, huge sizes, negative margins, edge gaps and extreme offsets.
+    for seed in 0u64..2_000 {
+        let mut rng = Lcg::new(seed);
+
+        let count = rng.gen_range_usize(0, 40);
+        let sizes: Vec<u32> = (0..count)
+            .map(|_| match rng.gen_range_u32(0, 10) {
+                0 | 1 => 0,
+                2 => rng.gen_range_u32(u32::MAX - 8, u32::MAX),
+                _ => rng.gen_range_u32(1, 30),
+            })
+            .collect();
+        let sizes = Arc::new(sizes);
+        let mut opts = VirtualizerOptions::new(count, {
+            let sizes = Arc::clone(&sizes);
+            move |i| sizes[i]
+        });
+        opts.gap = rng.gen_range_u32(0, 4);
+        opts.padding_start = rng.gen_range_u32(0, 8);
+        opts.padding_end = rng.gen_range_u32(0, 8);
+        opts.leading_gap = rng.gen_range_u32(0, 3);
+        opts.trailing_gap = rng.gen_range_u32(0, 3);
+        opts.scroll_margin = rng.gen_range_u64(0, 41) as i64 - 20;
+        opts.scroll_padding_start = rng.gen_range_u32(0, 5);
+        opts.scroll_padding_end = rng.gen_range_u32(0, 5);
+        opts.overscan = rng.gen_range_usize(0, 4);
+        if rng.gen_bool() {
+            opts.max_total_size = Some(rng.gen_range_u64(0, 300));
+        }
+        if rng.gen_range_u32(0, 4) == 0 {
+            opts.gap_after = Some(Arc::new(|i| (i % 3) as u32));
+        }
+        let mut v = Virtualizer::new(opts);
+
+        for _ in 0..8 {
+            if count > 0 && rng.gen_bool() {
+                let size = match rng.gen_range_u32(0, 4) {
+                    0 => 0,
+                    1 => u32::MAX,
+                    _ => rng.gen_range_u32(1, 30),
+                };
+                v.measure(rng.gen_range_usize(0, count), size);
+            }
+            v.set_viewport_size(match rng.gen_range_u32(0, 4) {
+                0 => 0,
+                1 => u32::MAX,
+                _ => rng.gen_range_u32(1, 120),
+            });
+            v.set_scroll_offset(match rng.gen_range_u32(0, 5) {
+                0 => 0,
+                1 => u64::MAX,
+                2 => v.max_scroll_offset(),
+                _ => rng.gen_range_u64(0, 400),
+            });
+
+            let visible = v.visible_range();
+            let virt = v.virtual_range();
+            assert!(virt.start_index <= virt.end_index && virt.end_index <= count);
+            if visible.is_empty() {
+                assert!(virt.is_empty(), "seed={seed}");
+            } else {
+                assert!(visible.end_index <= count);
+                assert!(virt.start_index <= visible.start_index, "seed={seed}");
+                assert!(visible.end_index <= virt.end_index, "seed={seed}");
+            }
+
+            // Yielded items are contiguous and span exactly `content_size_in` (starts clamped to 0
+            // by a negative margin are excluded).
+            let mut items = Vec::new();
+            v.for_each_virtual_item(|it| items.push(it));
+            assert_eq!(items.len(), virt.len());
+            let negative_margin = v.options().scroll_margin < 0;
+            items.retain(|it| !(negative_margin && it.start == 0));
+            for pair in items.windows(2) {
+                let gap = match &v.options().gap_after {
+                    Some(f) => f(pair[0].index),
+                    None => v.options().gap,
+                };
+                assert_eq!(
+                    pair[1].start,
+                    pair[0].end().saturating_add(gap as u64),
+                    "seed={seed}"
+                );
+            }
+            if let (Some(first), Some(last)) = (items.first(), items.last())
+                && first.index == virt.start_index
+            {
+                assert_eq!(
+                    last.end().saturating_sub(first.start),
+                    v.content_size_in(virt),
+                    "seed={seed}"
+                );
+            }
+
+            // Rendered (compressed) offsets stay monotonic and within the rendered extent.
+            let max = v.max_scroll_offset();
+            let rendered_end = v.to_rendered(max);
+            assert!(
+                v.to_rendered(v.scroll_offset().min(max)) <= rendered_end,
+                "seed={seed}"
+            );
+            assert_eq!(v.to_virtual(rendered_end), max, "seed={seed}");
+            assert_eq!(v.to_virtual(0), 0);
+
+            // Smoke the derived APIs for panics.
+            for align in [
+                Align::Start,
+                Align::Center,
+                Align::End,
+                Align::Auto,
+                Align::CenterIfNeeded,
+                Align::Nearest,
+            ] {
+                let target = v.scroll_to_index_offset(rng.gen_range_usize(0, count + 1), align);
+                assert!(target <= v.max_scroll_offset());
+                let _ = v.scroll_to_range_offset(0, count, align);
+            }
+            let probe = rng.gen_range_u64(0, 500);
+            let _ = v.hit_test(probe, |i| i % 5 == 0);
+            let _ = v.items_before_offset(probe);
+            let _ = v.center_offset(probe);
+            let _ = v.scroll_progress();
+            let _ = v.thumb_size_fraction();
+            let _ = v.uniform_window();
+            let _ = v.debug_report();
+            v.overscan_items_by_distance(|i, _| assert!(virt.contains(i) && !visible.contains(i)));
+        }
+
+        // Offset -> index round-trips at item starts (zero-size items share a start with the
+        // next item, and starts clamped to 0 by a negative margin are ambiguous).
+        for i in 0..count {
+            let start = v.item_start(i).unwrap();
+            if v.item_size(i) == Some(0) || (start == 0 && v.options().scroll_margin < 0) {
+                continue;
+            }
+            assert_eq!(v.index_at_offset(start), Some(i), "seed={seed} i={i}");
+            assert_eq!(v.item_bounds(i).unwrap().0, start);
+
+            // Scrolling an item to the start makes it the first visible item (unless clamped).
+            let target = v.scroll_to_index_offset(i, Align::Start);
+            if v.options().scroll_padding_start == 0
+                && v.viewport_size() > 0
+                && target < v.max_scroll_offset()
+            {
+                assert_eq!(target, start);
+                let visible = v.visible_range_for(target, v.viewport_size());
+                assert_eq!(visible.start_index, i, "seed={seed} i={i}");
+            }
+        }
+    }
+}
+
+#[test]
+fn most_centered_index_with_variable_sizes() {
+    // Starts: 0, 10, 40, 45, 105, 125, ...
+    let sizes = [10u32, 30, 5, 60, 20, 10, 10, 10];
+    let mut v = Virtualizer::new(VirtualizerOptions::new(sizes.len(), move |i| sizes[i]));
+    assert_eq!(v.most_centered_index(), None);
+
+    v.set_viewport_size(50);
+    // Viewport center 25: item 1 (center 25).
+    assert_eq!(v.most_centered_index(), Some(1));
+
+    // Viewport center 55: item 2 (center 42.5) beats item 3 (center 75).
+    v.set_scroll_offset(30);
+    assert_eq!(v.most_centered_index(), Some(2));
+
+    // Viewport center 70: item 3 (center 75) beats item 2 (42.5).
+    v.set_scroll_offset(45);
+    assert_eq!(v.most_centered_index(), Some(3));
+
+    v.set_enabled(false);
+    assert_eq!(v.most_centered_index(), None);
+}
+
+#[test]
+fn estimate_size_ctx_interpolates_between_measured_neighbors() {
+    let interpolate = |i: usize, ctx: &EstimateCtx<'_>| match (
+        ctx.nearest_measured_before(i),
+        ctx.nearest_measured_after(i),
+    ) {
+        (Some((a, sa)), Some((b, sb))) => {
+            let t = (i - a) as i64 * (sb as i64 - sa as i64) / (b - a) as i64;
+            (sa as i64 + t) as u32
+        }
+        (Some((_, s)), None) | (None, Some((_, s))) => s,
+        (None, None) => 20,
+    };
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |_| 1).with_estimate_size_ctx(Some(interpolate)),
+    );
+    assert_eq!(v.item_size(5), Some(20));
+
+    v.measure(2, 10);
+    v.measure(6, 50);
+    v.refresh_estimates_incremental();
+    let sizes: Vec<u32> = (0..10).map(|i| v.item_size(i).unwrap()).collect();
+    assert_eq!(sizes, vec![10, 10, 10, 20, 30, 40, 50, 50, 50, 50]);
+
+    // Full rebuilds see neighbours on both sides too.
+    v.sync_item_keys();
+    assert_eq!(v.item_size(4), Some(30));
+
+    // Appended items only see measured items before them.
+    v.set_count(12);
+    assert_eq!(v.item_size(11), Some(50));
+
+    v.set_estimate_size_ctx(None::<fn(usize, &EstimateCtx<'_>) -> u32>);
+    assert_eq!(v.item_size(4), Some(1));
+    assert_eq!(v.item_size(6), Some(50));
+}
+
+#[test]
+fn with_suspended_notifications_fires_once_with_final_state() {
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |_| 1).with_on_change_with_reason(Some({
+            let seen = Arc::clone(&seen);
+            move |v: &Virtualizer<u64>, reason: ChangeReason| {
+                seen.lock().unwrap().push((reason, v.count(), v.key_for(0)));
+            }
+        })),
+    );
+
+    let total = v.with_suspended_notifications(|v| {
+        v.set_count(50);
+        v.set_get_item_key(|i| 1_000 + i as u64);
+        v.batch_update(|v| v.set_overscan(4));
+        assert!(seen.lock().unwrap().is_empty());
+        v.total_size()
+    });
+    assert_eq!(total, 50);
+    assert_eq!(
+        core::mem::take(&mut *seen.lock().unwrap()),
+        vec![(ChangeReason::Options, 50, 1_000)]
+    );
+
+    // Nothing changed: no notification at all.
+    v.with_suspended_notifications(|v| v.count());
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[test]
+fn key_at_offset_matches_keyed_item_lookup() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new_with_key(20, |i| 5 + (i % 4) as u32, |i| i as u64 * 7)
+            .with_gap(1)
+            .with_padding(3, 2)
+            .with_scroll_margin(4),
+    );
+    for offset in 0..v.total_size() + 10 {
+        assert_eq!(
+            v.key_at_offset(offset),
+            v.virtual_item_keyed_for_offset(offset).map(|it| it.key)
+        );
+    }
+    assert_eq!(v.key_at_offset(7), Some(0));
+
+    v.set_enabled(false);
+    assert_eq!(v.key_at_offset(7), None);
+    v.set_enabled(true);
+    v.set_count(0);
+    assert_eq!(v.key_at_offset(0), None);
+}
+
+#[test]
+fn outlier_factor_rejects_extreme_measurements() {
+    let mut v =
+        Virtualizer::new(VirtualizerOptions::new(20, |_| 20).with_outlier_factor(Some(4.0)));
+    for i in 0..10 {
+        assert!(v.measure(i, 21 + i as u32));
+    }
+    assert_eq!(v.rejected_measurements(), 0);
+
+    // 100x the mean is rejected: the item keeps its estimate and stays unmeasured.
+    assert!(!v.measure(12, 2_000));
+    assert_eq!(v.item_size(12), Some(20));
+    assert!(!v.is_measured(12));
+    assert_eq!(v.rejected_measurements(), 1);
+
+    // Normal variation (2-3x) passes.
+    assert!(v.measure(13, 60));
+    assert!(v.apply_measurements(&[(14, 45), (15, 2_500)]));
+    assert_eq!(v.item_size(14), Some(45));
+    assert_eq!(v.item_size(15), Some(20));
+    assert_eq!(v.rejected_measurements(), 2);
+
+    v.reset_measurements();
+    assert_eq!(v.rejected_measurements(), 0);
+    // The mean restarts: the first measurement is always accepted.
+    assert!(v.measure(0, 2_000));
+}
+
+#[test]
+fn virtual_range_into_iterator() {
+    let r = VirtualRange {
+        start_index: 3,
+        end_index: 6,
+    };
+    assert_eq!(r.into_iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+    let mut seen = Vec::new();
+    for i in &r {
+        seen.push(i);
+    }
+    assert_eq!(seen, vec![3, 4, 5]);
+    assert_eq!(r.iter().rev().collect::<Vec<_>>(), vec![5, 4, 3]);
+    assert_eq!(r.iter().len(), r.len());
+
+    let empty = VirtualRange {
+        start_index: 4,
+        end_index: 4,
+    };
+    assert_eq!(empty.into_iter().count(), 0);
+    let inverted = VirtualRange {
+        start_index: 7,
+        end_index: 2,
+    };
+    assert_eq!(inverted.iter().count(), 0);
+}
+
+#[test]
+fn overscan_bands_nest_full_inside_placeholder() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10)
+            .with_overscan(2)
+            .with_placeholder_overscan(5),
+    );
+    v.set_viewport_and_scroll_clamped(50, 400);
+
+    let (full, placeholder) = v.overscan_bands();
+    assert_eq!(full, v.virtual_range());
+    assert_eq!(
+        full,
+        VirtualRange {
+            start_index: 38,
+            end_index: 47
+        }
+    );
+    assert_eq!(
+        placeholder,
+        VirtualRange {
+            start_index: 33,
+            end_index: 52
+        }
+    );
+    assert!(placeholder.start_index < full.start_index);
+    assert!(placeholder.end_index > full.end_index);
+
+    // Clamped at the edges.
+    v.set_scroll_offset(0);
+    let (full, placeholder) = v.overscan_bands();
+    assert_eq!(full.start_index, 0);
+    assert_eq!(placeholder.start_index, 0);
+    assert_eq!(placeholder.end_index, full.end_index + 5);
+
+    // Without a placeholder overscan, both bands match.
+    v.set_placeholder_overscan(0);
+    let (full, placeholder) = v.overscan_bands();
+    assert_eq!(full, placeholder);
+}
+
+#[test]
+fn content_size_before_and_after_match_manual_sums() {
+    let sizes = [10u32, 20, 30, 40, 50];
+    let gap = 3u64;
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(sizes.len(), move |i| sizes[i])
+            .with_gap(gap as u32)
+            .with_padding(7, 11)
+            .with_scroll_margin(100),
+    );
+
+    let total = v.total_size();
+    for i in 0..=sizes.len() + 2 {
+        let n = i.min(sizes.len());
+        let mut manual: u64 = 7 + sizes[..n].iter().map(|&s| s as u64 + gap).sum::<u64>();
+        if n == sizes.len() {
+            // No gap after the last item.
+            manual -= gap;
+        }
+        assert_eq!(v.content_size_before(i), manual, "index {i}");
+        assert_eq!(v.content_size_before(i) + v.content_size_after(i), total);
+        if i < sizes.len() {
+            // `item_start` additionally includes the scroll margin.
+            assert_eq!(v.item_start(i), Some(manual + 100));
+        }
+    }
+    assert_eq!(v.content_size_after(0), total - 7);
+    assert_eq!(v.content_size_after(sizes.len()), 11);
+
+    v.set_enabled(false);
+    assert_eq!(v.content_size_before(3), 0);
+    assert_eq!(v.content_size_after(3), 0);
+}
+
+#[test]
+fn sticky_footer_index_pins_unreached_footers() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_and_scroll_clamped(50, 0);
+
+    // A single footer at the end of the list stays pinned until it is fully scrolled into view.
+    assert_eq!(v.sticky_footer_index(&[99]), Some(99));
+    v.set_scroll_offset(945);
+    assert_eq!(v.sticky_footer_index(&[99]), Some(99));
+    v.set_scroll_offset(950);
+    assert_eq!(v.sticky_footer_index(&[99]), None);
+
+    // Mid-list footers (unsorted): the nearest unreached one wins.
+    let footers = [59, 19, 39, 99];
+    v.set_scroll_offset(0);
+    assert_eq!(v.sticky_footer_index(&footers), Some(19));
+    // Item 19 ends at 200: reached once the viewport bottom is at 200.
+    v.set_scroll_offset(150);
+    assert_eq!(v.sticky_footer_index(&footers), Some(39));
+    v.set_scroll_offset(149);
+    assert_eq!(v.sticky_footer_index(&footers), Some(19));
+
+    // Out-of-bounds indexes are ignored.
+    assert_eq!(v.sticky_footer_index(&[500]), None);
+    v.set_enabled(false);
+    assert_eq!(v.sticky_footer_index(&footers), None);
+}
+
+#[test]
+fn status_distinguishes_disabled_empty_and_active() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(0, |_| 10));
+    v.set_viewport_size(50);
+    assert_eq!(v.status(), VirtualizerStatus::Empty);
+
+    v.set_count(10);
+    assert_eq!(v.status(), VirtualizerStatus::Active);
+
+    v.set_viewport_size(0);
+    assert_eq!(v.status(), VirtualizerStatus::Empty);
+
+    v.set_viewport_size(50);
+    v.set_enabled(false);
+    assert_eq!(v.status(), VirtualizerStatus::Disabled);
+    // Both states look the same through the range queries.
+    assert!(v.virtual_range().is_empty());
+
+    v.set_enabled(true);
+    v.set_viewport_size(50);
+    assert_eq!(v.status(), VirtualizerStatus::Active);
+}
+
+#[test]
+fn scroll_to_index_revealing_returns_range_with_target() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(1_000, |i| 10 + (i % 7) as u32));
+    v.set_viewport_size(80);
+    for (index, align) in [
+        (500, Align::Start),
+        (20, Align::End),
+        (999, Align::Center),
+        (0, Align::Auto),
+        (731, Align::Nearest),
+    ] {
+        let range = v.scroll_to_index_revealing(index, align);
+        assert!(range.contains(index), "{index} {align:?} {range:?}");
+        assert_eq!(range, v.virtual_range());
+    }
+}
+
+#[test]
+fn measure_cross_tracks_max_cross_size() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    assert_eq!(v.max_cross_size(), 0);
+
+    assert!(v.measure_cross(2, 120));
+    assert!(v.measure_cross(5, 300));
+    assert!(v.measure_cross(7, 200));
+    assert_eq!(v.max_cross_size(), 300);
+    assert!(!v.measure_cross(5, 300));
+    assert!(!v.measure_cross(10, 999));
+
+    // Shrinking the widest item falls back to the next widest.
+    assert!(v.measure_cross(5, 50));
+    assert_eq!(v.max_cross_size(), 200);
+    // Main-axis layout is unaffected.
+    assert_eq!(v.total_size(), 100);
+
+    // Dropping items drops their cross sizes.
+    v.set_count(5);
+    assert_eq!(v.max_cross_size(), 120);
+
+    v.reset_measurements();
+    assert_eq!(v.max_cross_size(), 0);
+}
+
+#[test]
+fn retain_measurements_prunes_key_cache() {
+    use std::sync::Mutex;
+
+    let keys = Arc::new(Mutex::new((100u64..110).collect::<Vec<_>>()));
+    let mut v = Virtualizer::new(VirtualizerOptions::new_with_key(10, |_| 1, {
+        let keys = Arc::clone(&keys);
+        move |i| keys.lock().unwrap()[i]
+    }));
+    for i in 0..10 {
+        v.measure(i, 10 + i as u32);
+    }
+    assert_eq!(v.measurement_cache_len(), 10);
+
+    // Keep only even keys.
+    v.retain_measurements(|k| k % 2 == 0);
+    assert_eq!(v.measurement_cache_len(), 5);
+    assert_eq!(v.item_size(0), Some(10));
+    assert_eq!(v.item_size(1), Some(1));
+    assert!(!v.is_measured(1));
+
+    // Surviving keys still follow their data after a reorder.
+    keys.lock().unwrap().reverse();
+    v.sync_item_keys();
+    assert_eq!(v.item_size(9), Some(10)); // key 100
+    assert_eq!(v.item_size(1), Some(18)); // key 108
+    assert_eq!(v.item_size(0), Some(1)); // key 109 was pruned
+
+    // A no-op predicate does not notify.
+    let calls = Arc::new(AtomicUsize::new(0));
+    v.set_on_change(Some({
+        let calls = Arc::clone(&calls);
+        move |_: &Virtualizer<u64>, _| {
+            calls.fetch_add(1, Ordering::Relaxed);
+        }
+    }));
+    let before = calls.load(Ordering::Relaxed);
+    v.retain_measurements(|_| true);
+    assert_eq!(calls.load(Ordering::Relaxed), before);
+}
+
+#[test]
+fn adjust_item_size_applies_signed_deltas() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_and_scroll_clamped(50, 100);
+
+    // Item 2 is above the scroll offset: growth shifts the scroll position.
+    assert_eq!(v.adjust_item_size(2, 8), 8);
+    assert_eq!(v.item_size(2), Some(18));
+    assert_eq!(v.scroll_offset(), 108);
+    assert!(v.is_measured(2));
+
+    // Shrinking past zero clamps the size at 0.
+    assert_eq!(v.adjust_item_size(2, -20), -18);
+    assert_eq!(v.item_size(2), Some(0));
+    assert_eq!(v.scroll_offset(), 90);
+    assert_eq!(v.measurement_cache_len(), 1);
+
+    // Items below the viewport don't move the scroll position.
+    assert_eq!(v.adjust_item_size(50, 5), 0);
+    assert_eq!(v.item_size(50), Some(15));
+    assert_eq!(v.scroll_offset(), 90);
+
+    assert_eq!(v.adjust_item_size(100, 5), 0);
+}
+
+#[test]
+fn container_and_list_offsets_round_trip() {
+    for margin in [0i64, 40, -25] {
+        let v = Virtualizer::new(
+            VirtualizerOptions::new(50, |_| 10)
+                .with_padding(5, 0)
+                .with_scroll_margin(margin),
+        );
+        for list in [0u64, 1, 25, 37, 499] {
+            let container = v.list_offset_to_container(list);
+            if margin >= 0 || list >= margin.unsigned_abs() {
+                assert_eq!(
+                    v.container_offset_to_list(container),
+                    list,
+                    "{margin} {list}"
+                );
+            }
+        }
+        for i in [0usize, 3, 49] {
+            let in_list = v.item_start_in_list(i).unwrap();
+            assert_eq!(in_list, 5 + 10 * i as u64);
+            assert_eq!(
+                v.list_offset_to_container(in_list),
+                v.item_start(i).unwrap()
+            );
+        }
+        assert_eq!(v.item_start_in_list(50), None);
+    }
+}
+
+#[test]
+fn initial_offset_end_scrolls_to_bottom_on_first_viewport() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10_000, |_| 20).with_initial_offset(InitialOffset::End),
+    );
+    // Nothing to resolve against yet.
+    assert_eq!(v.scroll_offset(), 0);
+
+    v.set_viewport_size(400);
+    assert_eq!(v.scroll_offset(), 200_000 - 400);
+    assert_eq!(v.virtual_range().end_index, 10_000);
+
+    // Only the first viewport triggers it.
+    v.set_scroll_offset(1_000);
+    v.set_viewport_size(500);
+    assert_eq!(v.scroll_offset(), 1_000);
+
+    // With a known `initial_rect`, it resolves at construction.
+    let v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10)
+            .with_initial_rect(Some(Rect { main: 50, cross: 0 }))
+            .with_initial_offset(InitialOffset::End),
+    );
+    assert_eq!(v.scroll_offset(), 950);
+
+    // `set_scroll_rect` resolves it too, and re-enabling re-arms it.
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10).with_initial_offset(InitialOffset::End),
+    );
+    v.set_scroll_rect(Rect {
+        main: 100,
+        cross: 10,
+    });
+    assert_eq!(v.scroll_offset(), 900);
+    v.set_enabled(false);
+    v.set_enabled(true);
+    assert_eq!(v.scroll_offset(), 0);
+    v.set_viewport_size(200);
+    assert_eq!(v.scroll_offset(), 800);
+}
+
+#[test]
+fn line_at_offset_resolves_wrapped_lines() {
+    // Item 1 wraps onto 3 lines of 20px each; the others are single 20px lines.
+    let v =
+        Virtualizer::new(VirtualizerOptions::new(4, |i| if i == 1 { 60 } else { 20 }).with_gap(4));
+    let lines = |i: usize| if i == 1 { 3 } else { 1 };
+
+    assert_eq!(v.line_at_offset(0, lines), Some((0, 0)));
+    assert_eq!(v.line_at_offset(19, lines), Some((0, 0)));
+    // Item 1 starts at 24.
+    assert_eq!(v.line_at_offset(24, lines), Some((1, 0)));
+    assert_eq!(v.line_at_offset(43, lines), Some((1, 0)));
+    assert_eq!(v.line_at_offset(44, lines), Some((1, 1)));
+    assert_eq!(v.line_at_offset(64, lines), Some((1, 2)));
+    assert_eq!(v.line_at_offset(83, lines), Some((1, 2)));
+    // Item 2 starts at 88.
+    assert_eq!(v.line_at_offset(88, lines), Some((2, 0)));
+    // Zero line counts are treated as a single line.
+    assert_eq!(v.line_at_offset(50, |_| 0), Some((1, 0)));
+
+    let empty = Virtualizer::new(VirtualizerOptions::new(0, |_| 20));
+    assert_eq!(empty.line_at_offset(0, lines), None);
+}
+
+#[test]
+fn set_scroll_offset_f64_rounds_half_to_even() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_size(50);
+
+    for (input, expected) in [
+        (10.4, 10),
+        (10.5, 10),
+        (10.6, 11),
+        (11.5, 12),
+        (0.5, 0),
+        (-3.7, 0),
+        (f64::NAN, 0),
+        (123.0, 123),
+    ] {
+        v.set_scroll_offset_f64(input);
+        assert_eq!(v.scroll_offset(), expected, "{input}");
+    }
+    v.set_scroll_offset_f64(f64::INFINITY);
+    assert_eq!(v.scroll_offset(), u64::MAX);
+
+    v.apply_scroll_offset_event_f64(42.5, 0);
+    assert_eq!(v.scroll_offset(), 42);
+    assert!(v.is_scrolling());
+}
+
+#[test]
+fn measure_many_into_reports_changed_indexes() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    let mut changed = vec![99];
+
+    v.measure_many_into([(3, 12), (1, 10), (5, 20), (42, 7)], &mut changed);
+    assert_eq!(changed, vec![3, 5]);
+    assert!(v.is_measured(1));
+
+    // Re-applying the same sizes is a no-op.
+    let calls = Arc::new(AtomicUsize::new(0));
+    v.set_on_change(Some({
+        let calls = Arc::clone(&calls);
+        move |_: &Virtualizer, _| {
+            calls.fetch_add(1, Ordering::Relaxed);
+        }
+    }));
+    let before = calls.load(Ordering::Relaxed);
+    v.measure_many_into([(3, 12), (1, 10), (5, 20)], &mut changed);
+    assert!(changed.is_empty());
+    assert_eq!(calls.load(Ordering::Relaxed), before);
+
+    v.measure_many_into([(5, 20), (3, 8)], &mut changed);
+    assert_eq!(changed, vec![3]);
+    assert_eq!(v.item_size(3), Some(8));
+}
+
+#[test]
+fn always_pinned_items_are_overlays_outside_the_flow() {
+    let baseline = Virtualizer::new(VirtualizerOptions::new(8, |_| 10).with_gap(2));
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(10, |i| if i < 2 { 30 } else { 10 })
+            .with_gap(2)
+            .with_always_pinned(vec![1, 0, 1]),
+    );
+    assert_eq!(v.options().always_pinned, vec![0, 1]);
+    assert!(v.is_always_pinned(1));
+    assert!(!v.is_always_pinned(2));
+
+    // The pinned items take no space: the layout matches a list without them.
+    assert_eq!(v.total_size(), baseline.total_size());
+    assert_eq!(v.item_start(2), Some(0));
+
+    // Measuring a pinned item updates its overlay size only.
+    assert!(!v.measure(0, 44));
+    assert_eq!(v.total_size(), baseline.total_size());
+
+    v.set_viewport_and_scroll_clamped(30, 24);
+    let mut rendered = Vec::new();
+    v.for_each_virtual_item(|it| rendered.push(it.index));
+    assert!(!rendered.contains(&0) && !rendered.contains(&1));
+    let mut indexes = Vec::new();
+    v.for_each_virtual_index(|i| indexes.push(i));
+    assert_eq!(indexes, rendered);
+
+    let mut pinned = Vec::new();
+    v.for_each_pinned_item(|it| pinned.push(it));
+    assert_eq!(
+        pinned,
+        vec![
+            VirtualItem {
+                index: 0,
+                start: 24,
+                size: 44
+            },
+            VirtualItem {
+                index: 1,
+                start: 68,
+                size: 30
+            },
+        ]
+    );
+
+    // Unpinning restores the items to the flow, with the cached measurement.
+    v.set_always_pinned(Vec::new());
+    assert_eq!(v.item_size(0), Some(44));
+    assert_eq!(v.total_size(), baseline.total_size() + 44 + 30 + 2 * 2);
+}
+
+#[test]
+fn visible_and_virtual_item_counts() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10_000, |_| 10).with_overscan(3));
+    v.set_viewport_and_scroll_clamped(120, 1_005);
+    // Items 100..=112 intersect [1005, 1125).
+    assert_eq!(v.visible_item_count(), 13);
+    assert_eq!(v.virtual_item_count(), 19);
+
+    v.set_range_extractor(Some(|r: Range, emit: &mut dyn FnMut(usize)| {
+        let mut e = IndexEmitter::new(r, emit);
+        e.emit_pinned(0);
+        e.emit_pinned(50);
+        e.emit_overscanned();
+        e.emit_pinned(9_999);
+    }));
+    let mut emitted = 0;
+    v.for_each_virtual_index(|_| emitted += 1);
+    assert_eq!(v.virtual_item_count(), emitted);
+    assert_eq!(emitted, 22);
+    assert_eq!(v.visible_item_count(), 13);
+
+    v.set_enabled(false);
+    assert_eq!(v.visible_item_count(), 0);
+    assert_eq!(v.virtual_item_count(), 0);
+}
+
+#[test]
+fn viewport_larger_than_total_size_yields_exactly_all_items() {
+    let full = VirtualRange {
+        start_index: 0,
+        end_index: 25,
+    };
+    for (padding, margin) in [(0u32, 0i64), (7, 0), (7, 30), (0, 1)] {
+        let mut v = Virtualizer::new(
+            VirtualizerOptions::new(25, |i| 3 + (i % 4) as u32)
+                .with_gap(1)
+                .with_padding(padding, padding)
+                .with_scroll_margin(margin)
+                .with_overscan(5),
+        );
+        // The viewport must also cover a positive `scroll_margin` (container space).
+        let smallest = (v.total_size() + margin.max(0) as u64) as u32 + 1;
+        for viewport in [smallest, 10_000, u32::MAX] {
+            for offset in [0, v.total_size() / 2, 5_000, u64::MAX] {
+                v.set_viewport_and_scroll(viewport, offset);
+                let ctx = (padding, margin, viewport, offset);
+                assert_eq!(v.visible_range(), full, "{ctx:?}");
+                assert_eq!(v.virtual_range(), full, "{ctx:?}");
+                assert_eq!(v.visible_range_for(offset, viewport), full, "{ctx:?}");
+                let mut last = None;
+                v.for_each_virtual_item(|it| last = Some(it.index));
+                assert_eq!(last, Some(24), "{ctx:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn on_structural_change_fires_only_for_structural_updates() {
+    use std::sync::Mutex;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10).with_on_structural_change(Some({
+            let seen = Arc::clone(&seen);
+            move |c| seen.lock().unwrap().push(c)
+        })),
+    );
+    let take = || core::mem::take(&mut *seen.lock().unwrap());
+    // Construction is not a change.
+    assert_eq!(take(), vec![]);
+
+    v.set_viewport_size(50);
+    v.set_scroll_offset(200);
+    v.apply_scroll_offset_event(300, 10);
+    v.measure(3, 12);
+    assert_eq!(take(), vec![]);
+
+    v.set_count(120);
+    assert_eq!(take(), vec![StructuralChange::Count]);
+    v.set_count(120);
+    assert_eq!(take(), vec![]);
+
+    v.set_gap(4);
+    assert_eq!(take(), vec![StructuralChange::Layout]);
+
+    v.set_estimate_size(|_| 20);
+    v.sync_item_keys();
+    assert_eq!(
+        take(),
+        vec![StructuralChange::Estimates, StructuralChange::Estimates]
+    );
+
+    // Fires immediately, even inside a batch.
+    v.batch_update(|v| {
+        v.set_count(10);
+        assert_eq!(take(), vec![StructuralChange::Count]);
+    });
+}
+
+#[test]
+fn scroll_margin_is_applied_once_when_clamping() {
+    // 100 items x 10px = 1000px of content, 100px viewport.
+    for (margin, max_container, max_list) in
+        [(30i64, 930u64, 900u64), (-20, 880, 900), (0, 900, 900)]
+    {
+        let mut v = Virtualizer::new(
+            VirtualizerOptions::new(100, |_| 10)
+                .with_scroll_margin(margin)
+                .with_overscan(0),
+        );
+        v.set_viewport_size(100);
+        assert_eq!(v.max_scroll_offset(), max_container, "{margin}");
+        assert_eq!(v.max_scroll_offset_in_list(), max_list, "{margin}");
+
+        // Over-scrolled: clamps to the last page, [900, 1000) in list space.
+        v.set_scroll_offset(u64::MAX);
+        let expected = VirtualRange {
+            start_index: 90,
+            end_index: 100,
+        };
+        assert_eq!(v.visible_range(), expected, "{margin}");
+        assert_eq!(v.virtual_range(), expected, "{margin}");
+
+        // At the exact max offset the range is the same.
+        v.set_scroll_offset(max_container);
+        assert_eq!(v.visible_range(), expected, "{margin}");
+        assert_eq!(v.scroll_offset_in_list(), max_list, "{margin}");
+    }
+
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10).with_scroll_margin(30));
+    v.set_viewport_size(100);
+    v.set_enabled(false);
+    assert_eq!(v.max_scroll_offset_in_list(), 0);
+}
+
+#[test]
+fn scroll_to_index_offset_with_margins_keeps_a_comfort_zone() {
+    // 10px rows, 100px viewport, 20px lead and trail margins: the comfort zone is [20, 80).
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.set_viewport_and_scroll_clamped(100, 0);
+
+    // Moving the selection within the zone doesn't scroll.
+    for index in 2..=7 {
+        assert_eq!(
+            v.scroll_to_index_offset_with_margins(index, 20, 20),
+            0,
+            "{index}"
+        );
+    }
+    // One past the zone: scroll just enough (item 8 ends at 90 -> needs 10px).
+    assert_eq!(v.scroll_to_index_offset_with_margins(8, 20, 20), 10);
+    assert_eq!(v.scroll_to_index_offset_with_margins(20, 20, 20), 130);
+
+    // Moving back up scrolls only once the lead margin is violated.
+    v.set_scroll_offset(130);
+    assert_eq!(v.scroll_to_index_offset_with_margins(15, 20, 20), 130);
+    assert_eq!(v.scroll_to_index_offset_with_margins(14, 20, 20), 120);
+
+    // Near the edges the offset is clamped.
+    assert_eq!(v.scroll_to_index_offset_with_margins(0, 20, 20), 0);
+    assert_eq!(v.scroll_to_index_offset_with_margins(99, 20, 20), 900);
+
+    // Margins larger than the viewport keep the item start at the lead margin.
+    assert_eq!(v.scroll_to_index_offset_with_margins(50, 60, 60), 440);
+}
+
+#[test]
+fn options_config_round_trips_plain_data_fields() {
+    let opts = VirtualizerOptions::new(50, |_| 10)
+        .with_overscan(7)
+        .with_gap(3)
+        .with_padding(11, 13)
+        .with_scroll_padding(2, 4)
+        .with_scroll_margin(-5)
+        .with_initial_offset_value(120)
+        .with_item_size_bounds(2, Some(400));
+    let config = opts.to_config();
+    assert_eq!(config.overscan, 7);
+    assert_eq!(config.initial_offset, Some(120));
+
+    let mut restored = VirtualizerOptions::new(3, |_| 1);
+    restored.apply_config(&config);
+    assert_eq!(restored.to_config(), config);
+    assert_eq!(restored.count, 3);
+    assert_eq!(restored.overscan, 7);
+    assert_eq!(restored.gap, 3);
+    assert_eq!((restored.padding_start, restored.padding_end), (11, 13));
+    assert_eq!(restored.scroll_margin, -5);
+    assert!(matches!(restored.initial_offset, InitialOffset::Value(120)));
+
+    // Non-value initial offsets are not captured, and are kept on apply.
+    let mut end = VirtualizerOptions::new(3, |_| 1).with_initial_offset(InitialOffset::End);
+    let end_config = end.to_config();
+    assert_eq!(end_config.initial_offset, None);
+    end.apply_config(&end_config);
+    assert!(matches!(end.initial_offset, InitialOffset::End));
+
+    assert_eq!(
+        VirtualizerOptionsConfig::default(),
+        VirtualizerOptions::new(0, |_| 1).to_config()
+    );
+}
+
+#[test]
+fn indices_for_keys_resolves_sorted_indexes() {
+    let v = Virtualizer::new(
+        VirtualizerOptions::new_with_key(10, |_| 1, |i| 100 + i as u64)
+            .with_maintain_key_index(true),
+    );
+    let lookup = |k: &u64| k.checked_sub(100).map(|i| i as usize);
+
+    assert_eq!(
+        v.indices_for_keys([107, 102, 105, 102], lookup),
+        vec![2, 5, 7]
+    );
+    // Unknown and out-of-bounds keys are skipped.
+    assert_eq!(v.indices_for_keys([5, 150, 103], lookup), vec![3]);
+    // Works with the internal key index too.
+    assert_eq!(
+        v.indices_for_keys([109, 100, 42], |k| v.index_for_key(k)),
+        vec![0, 9]
+    );
+    assert!(v.indices_for_keys([], lookup).is_empty());
+}
+
+#[test]
+fn overscan_px_covers_a_pixel_band_instead_of_an_item_count() {
+    // Items 0..10 are 100px, items 10.. are 10px.
+    let opts = VirtualizerOptions::new(100, |i| if i < 10 { 100 } else { 10 })
+        .with_overscan(1)
+        .with_overscan_px(Some(50))
+        .with_initial_rect(Some(Rect {
+            main: 100,
+            cross: 0,
+        }));
+    let mut v = Virtualizer::new(opts);
+
+    // Visible: items 10..20 (1000..1100). 50px before reaches into item 9, 50px after covers 5
+    // small items.
+    v.set_scroll_offset(1000);
+    assert_eq!(
+        v.visible_range(),
+        VirtualRange {
+            start_index: 10,
+            end_index: 20
+        }
+    );
+    assert_eq!(
+        v.virtual_range(),
+        VirtualRange {
+            start_index: 9,
+            end_index: 25
+        }
+    );
+
+    let mut seen = Vec::new();
+    v.for_each_virtual_index(|i| seen.push(i));
+    assert_eq!(seen, (9..25).collect::<Vec<_>>());
+    let mut items = Vec::new();
+    v.for_each_virtual_item(|it| items.push(it.index));
+    assert_eq!(items, seen);
+    assert_eq!(v.overscan_bands().0, v.virtual_range());
+
+    // A zero band disables overscan entirely; `None` falls back to the item count.
+    v.set_overscan_px(Some(0));
+    assert_eq!(v.virtual_range(), v.visible_range());
+    v.set_overscan_px(None);
+    assert_eq!(
+        v.virtual_range(),
+        VirtualRange {
+            start_index: 9,
+            end_index: 21
+        }
+    );
+
+    // The band is clamped to the list bounds.
+    v.set_overscan_px(Some(u32::MAX));
+    assert_eq!(
+        v.virtual_range(),
+        VirtualRange {
+            start_index: 0,
+            end_index: 100
+        }
+    );
+}
+
+#[test]
+fn appending_items_extends_the_tree_without_touching_the_prefix() {
+    let n = 100_000;
+    let mut v = Virtualizer::new(VirtualizerOptions::new(n, |_| 10).with_gap(2));
+    v.measure(0, 40);
+    v.measure(n - 1, 25);
+    let before: Vec<_> = (0..n).map(|i| v.item_size(i).unwrap()).collect();
+    let total = v.total_size();
+
+    v.set_count(n + 10);
+
+    assert!((0..n).all(|i| v.item_size(i) == Some(before[i])));
+    assert!(v.is_measured(0) && v.is_measured(n - 1) && !v.is_measured(n));
+    // The old last item gains a trailing gap; each new item adds its size plus a gap.
+    assert_eq!(v.total_size(), total + 2 + 10 * 10 + 9 * 2);
+    assert_eq!(v.item_start(n), Some(v.item_end(n - 1).unwrap() + 2));
+    assert_eq!(
+        v.item_start(n + 9).unwrap() + 10,
+        v.total_size() - v.options().padding_end as u64
+    );
+}
+
+#[test]
+fn snap_offset_to_grid_rounds_to_nearest_multiple() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    assert_eq!(v.snap_offset_to_grid(13, 4), 12);
+    assert_eq!(v.snap_offset_to_grid(14, 4), 16);
+    assert_eq!(v.snap_offset_to_grid(16, 4), 16);
+    assert_eq!(v.snap_offset_to_grid(4, 3), 3);
+    assert_eq!(v.snap_offset_to_grid(5, 3), 6);
+    assert_eq!(v.snap_offset_to_grid(13, 0), 13);
+    assert_eq!(v.snap_offset_to_grid(13, 1), 13);
+    assert_eq!(v.snap_offset_to_grid(u64::MAX, 4), u64::MAX);
+
+    v.set_scroll_offset(13);
+    assert_eq!(v.scroll_offset(), 13);
+    v.set_scroll_grid(Some(4));
+    v.set_scroll_offset(13);
+    assert_eq!(v.scroll_offset(), 12);
+    v.apply_scroll_offset_event(14, 0);
+    assert_eq!(v.scroll_offset(), 16);
+
+    // Clamped offsets are not rounded up past the end.
+    v.set_viewport_size(97);
+    assert_eq!(v.max_scroll_offset(), 903);
+    v.set_scroll_offset_clamped(u64::MAX);
+    assert_eq!(v.scroll_offset(), 900);
+}
+
+#[test]
+fn contiguous_measured_prefix_and_measured_ratio() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    assert_eq!(v.contiguous_measured_prefix(), 0);
+    for i in 0..10 {
+        v.measure(i, 20);
+    }
+    v.measure(50, 20);
+    assert_eq!(v.contiguous_measured_prefix(), 10);
+
+    let r = |start_index, end_index| VirtualRange {
+        start_index,
+        end_index,
+    };
+    assert_eq!(v.measured_ratio_in(r(0, 10)), 1.0);
+    assert_eq!(v.measured_ratio_in(r(5, 15)), 0.5);
+    assert_eq!(v.measured_ratio_in(r(50, 500)), 0.02);
+    assert_eq!(v.measured_ratio_in(r(20, 20)), 1.0);
+    assert_eq!(v.measured_ratio_in(r(200, 300)), 1.0);
+}
+
+#[test]
+fn align_fraction_interpolates_between_start_and_end() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(1_000, |_| 20)
+            .with_scroll_padding(5, 7)
+            .with_initial_rect(Some(Rect {
+                main: 100,
+                cross: 0,
+            })),
+    );
+    v.set_scroll_offset(3_000);
+    for index in [0, 10, 500, 999] {
+        assert_eq!(
+            v.scroll_to_index_offset(index, Align::Fraction(0.0)),
+            v.scroll_to_index_offset(index, Align::Start)
+        );
+        assert_eq!(
+            v.scroll_to_index_offset(index, Align::Fraction(1.0)),
+            v.scroll_to_index_offset(index, Align::End)
+        );
+    }
+
+    // Item 500 starts at 10_000; Start = 9_995, End = 10_020 + 7 - 100 = 9_927.
+    assert_eq!(v.scroll_to_index_offset(500, Align::Fraction(0.3)), 9_975);
+    assert_eq!(v.scroll_to_index_offset(500, Align::Fraction(0.5)), 9_961);
+    assert_eq!(v.scroll_to_index_offset(500, Align::Fraction(-1.0)), 9_995);
+    assert_eq!(v.scroll_to_index_offset(500, Align::Fraction(2.0)), 9_927);
+    assert_eq!(
+        v.scroll_to_index_offset(500, Align::Fraction(f32::NAN)),
+        9_995
+    );
+}
+
+#[test]
+fn take_dirty_reports_changes_since_the_last_poll() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(100, |_| 10));
+    v.take_dirty();
+    assert!(!v.take_dirty());
+
+    v.set_scroll_offset(50);
+    assert!(v.take_dirty());
+    assert!(!v.take_dirty());
+
+    // No-op setters don't mark the virtualizer dirty.
+    v.set_scroll_offset(50);
+    v.set_count(100);
+    assert!(!v.take_dirty());
+
+    v.batch_update(|v| {
+        v.set_viewport_size(30);
+        v.measure(0, 20);
+    });
+    assert!(v.take_dirty());
+    assert!(!v.take_dirty());
+
+    // Re-measuring the same size, re-setting plain options and empty option updates are no-ops.
+    v.measure(0, 20);
+    v.measure_unadjusted(0, 20);
+    v.resize_item(0, 20);
+    let overscan = v.options().overscan;
+    v.set_overscan(overscan);
+    v.set_overscan_px(v.options().overscan_px);
+    v.set_padding(v.options().padding_start, v.options().padding_end);
+    v.set_scroll_margin(v.options().scroll_margin);
+    v.set_freeze_measured(v.options().freeze_measured);
+    v.refresh_estimates_incremental();
+    v.update_options(|_| {});
+    assert!(!v.take_dirty());
+
+    v.set_overscan(overscan + 1);
+    assert!(v.take_dirty());
+    v.update_options(|o| o.gap = 4);
+    assert!(v.take_dirty());
+}
+
+#[test]
+fn prefetch_range_px_covers_a_pixel_budget_in_the_scroll_direction() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(1_000, |_| 10)
+            .with_overscan(2)
+            .with_initial_rect(Some(Rect {
+                main: 100,
+                cross: 0,
+            })),
+    );
+    let r = |start_index, end_index| VirtualRange {
+        start_index,
+        end_index,
+    };
+    assert_eq!(v.prefetch_range_px(100), r(0, 0));
+
+    v.set_scroll_offset(1_000);
+    // Rendered: 98..112.
+    assert_eq!(v.prefetch_range_px(100), r(112, 122));
+    assert_eq!(v.prefetch_range_px(95), r(112, 122));
+    assert_eq!(v.prefetch_range_px(0), r(0, 0));
+
+    v.set_scroll_offset(500);
+    // Rendered: 48..62.
+    assert_eq!(v.prefetch_range_px(100), r(38, 48));
+    assert_eq!(v.prefetch_range_px(10_000), r(0, 48));
+
+    v.set_scroll_offset(9_900);
+    assert_eq!(v.prefetch_range_px(100), r(1_000, 1_000));
+}
+
+#[test]
+fn buffered_index_emitter_sorts_and_dedups_unordered_pushes() {
+    let run = |f: &dyn Fn(&mut BufferedIndexEmitter<'_>)| {
+        let range = Range {
+            start_index: 50,
+            end_index: 55,
+            overscan: 2,
+            count: 100,
+        };
+        let mut out = Vec::new();
+        let mut emit = |i| out.push(i);
+        let mut e = IndexEmitter::buffered(range, &mut emit);
+        f(&mut e);
+        e.finish();
+        out
+    };
+
+    assert_eq!(
+        run(&|e| {
+            e.push(90);
+            e.push(3);
+            e.push_overscanned();
+            e.push(0);
+            e.push(50);
+            e.push(3);
+        }),
+        [0, 3, 48, 49, 50, 51, 52, 53, 54, 55, 56, 90]
+    );
+
+    // Overlapping spans and spans past `count` are merged and clamped.
+    assert_eq!(
+        run(&|e| {
+            e.push_range(97, 120);
+            e.push_visible();
+            e.push_range(53, 58);
+        }),
+        [50, 51, 52, 53, 54, 55, 56, 57, 97, 98, 99]
+    );
+
+    // More pushes than the inline capacity spill to the heap.
+    let expected: Vec<usize> = (0..40).collect();
+    assert_eq!(
+        run(&|e| {
+            for i in (0..40).rev() {
+                e.push(i);
+            }
+        }),
+        expected
+    );
+}
+
+#[test]
+fn buffered_index_emitter_flushes_on_drop() {
+    let mut opts = VirtualizerOptions::new(100, |_| 1).with_overscan(0);
+    opts.range_extractor = Some(Arc::new(|r: Range, emit: &mut dyn FnMut(usize)| {
+        let mut e = IndexEmitter::buffered(r, emit);
+        e.push(99);
+        e.push_visible();
+        e.push(0);
+    }));
+    let mut v = Virtualizer::new(opts);
+    v.set_viewport_size(3);
+    v.set_scroll_offset(50);
+    let mut seen = Vec::new();
+    v.for_each_virtual_index(|i| seen.push(i));
+    assert_eq!(seen, [0, 50, 51, 52, 99]);
+}
+
+#[test]
+fn scroll_by_items_moves_by_real_item_sizes() {
+    // Sizes cycle 10, 20, 30.
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(30, |i| 10 + (i as u32 % 3) * 10)
+            .with_initial_rect(Some(Rect { main: 50, cross: 0 })),
+    );
+    assert_eq!(v.scroll_by_items(1, Align::Start), 10);
+    assert_eq!(v.scroll_by_items(1, Align::Start), 30);
+    assert_eq!(v.scroll_by_items(5, Align::Start), 130);
+    assert_eq!(v.index_at_offset(v.scroll_offset()), Some(7));
+    assert_eq!(v.scroll_by_items(-1, Align::Start), 120);
+    assert_eq!(v.scroll_by_items(-5, Align::Start), 10);
+    assert_eq!(v.index_at_offset(v.scroll_offset()), Some(1));
+
+    // Clamped at both ends.
+    assert_eq!(v.scroll_by_items(-100, Align::Start), 0);
+    assert_eq!(
+        v.scroll_by_items(i64::MAX, Align::End),
+        v.max_scroll_offset()
+    );
+    assert_eq!(v.scroll_by_items(i64::MIN, Align::Start), 0);
+
+    let mut empty = Virtualizer::new(VirtualizerOptions::new(0, |_| 10));
+    assert_eq!(empty.scroll_by_items(3, Align::Start), 0);
+}
+
+#[test]
+fn scroll_by_items_steps_past_the_scroll_padding() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(30, |_| 10)
+            .with_scroll_padding(5, 0)
+            .with_initial_rect(Some(Rect { main: 50, cross: 0 })),
+    );
+    assert_eq!(v.scroll_by_items(1, Align::Start), 5);
+    assert_eq!(v.scroll_by_items(1, Align::Start), 15);
+    assert_eq!(v.scroll_by_items(1, Align::Start), 25);
+    assert_eq!(v.scroll_by_items(-1, Align::Start), 15);
+}
+
+#[test]
+fn provisional_count_ignores_measurements() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(50, |_| 10).with_provisional(true));
+    assert!(v.is_provisional());
+    assert_eq!(v.total_size(), 500);
+
+    assert!(!v.measure(0, 40));
+    v.measure_unadjusted(1, 40);
+    assert_eq!(v.resize_item(2, 40), 0);
+    assert!(!v.apply_measurements(&[(3, 40), (4, 10)]));
+    assert_eq!(v.total_size(), 500);
+    assert!((0..5).all(|i| !v.is_measured(i)));
+    assert!(v.export_measurement_cache().is_empty());
+
+    v.set_provisional(false);
+    assert!(!v.is_provisional());
+    assert!(v.measure(0, 40));
+    assert!(v.is_measured(0));
+    assert_eq!(v.total_size(), 530);
+}
+
+#[test]
+fn uniform_estimate_rebuilds_without_per_item_closure_calls() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let c = Arc::clone(&calls);
+    let n = 1_000_000;
+    let opts = VirtualizerOptions::new(n, move |_| {
+        c.fetch_add(1, Ordering::Relaxed);
+        10
+    })
+    .with_uniform_estimate(30)
+    .with_gap(2);
+    let mut v = Virtualizer::new(opts);
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+    assert_eq!(v.total_size(), 30 * n as u64 + 2 * (n as u64 - 1));
+    assert_eq!(v.item_start(500_000), Some(500_000 * 32));
+    assert_eq!(v.index_at_offset(500_000 * 32 + 31), Some(500_000));
+
+    v.set_count(n + 5);
+    v.set_gap(4);
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+    assert_eq!(v.total_size(), 30 * (n as u64 + 5) + 4 * (n as u64 + 4));
+
+    // Measured items still win over the uniform estimate.
+    v.measure(0, 100);
+    v.set_uniform_estimate(Some(20));
+    assert_eq!(v.item_size(0), Some(100));
+    assert_eq!(v.item_size(1), Some(20));
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+    v.set_uniform_estimate(None);
+    assert_eq!(calls.load(Ordering::Relaxed), n + 5 - 1);
+    assert_eq!(v.item_size(1), Some(10));
+}
+
+#[test]
+fn fenwick_from_uniform_matches_from_sizes() {
+    use crate::fenwick::Fenwick;
+    for n in [0, 1, 2, 3, 7, 8, 9, 100] {
+        let uniform = Fenwick::from_uniform(n, 7, 3);
+        let sizes = Fenwick::from_sizes(&vec![7; n], 3);
+        assert_eq!(uniform.total(), sizes.total(), "n={n}");
+        for i in 0..=n {
+            assert_eq!(uniform.prefix_sum(i), sizes.prefix_sum(i), "n={n} i={i}");
+        }
+    }
+}
+
+#[test]
+fn hit_region_classifies_items_gaps_paddings_and_margins() {
+    // Container: margin 0..20, padding_start 20..25, items 25..35, 37..47, 49..59 (gaps of 2),
+    // padding_end 59..67.
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(3, |_| 10)
+            .with_scroll_margin(20)
+            .with_padding(5, 8)
+            .with_gap(2),
+    );
+    use HitRegion::*;
+    let cases = [
+        (0, BeforeList),
+        (19, BeforeList),
+        (20, PaddingStart),
+        (24, PaddingStart),
+        (25, Item(0)),
+        (34, Item(0)),
+        (35, Gap(0)),
+        (36, Gap(0)),
+        (37, Item(1)),
+        (47, Gap(1)),
+        (48, Gap(1)),
+        (49, Item(2)),
+        (58, Item(2)),
+        (59, PaddingEnd),
+        (66, PaddingEnd),
+        (67, AfterList),
+        (u64::MAX, AfterList),
+    ];
+    for (offset, expected) in cases {
+        assert_eq!(v.hit_region(offset), expected, "offset {offset}");
+    }
+    // `index_at_offset` clamps instead.
+    assert_eq!(v.index_at_offset(66), Some(2));
+
+    // A negative margin shifts the list up instead.
+    v.set_scroll_margin(-3);
+    assert_eq!(v.hit_region(0), PaddingStart);
+    assert_eq!(v.hit_region(2), Item(0));
+
+    v.set_count(0);
+    assert_eq!(v.hit_region(0), PaddingStart);
+    assert_eq!(v.hit_region(2), PaddingEnd);
+    assert_eq!(v.hit_region(10), AfterList);
+
+    v.set_enabled(false);
+    assert_eq!(v.hit_region(0), AfterList);
+}
+
+#[test]
+fn set_options_rekeys_when_window_base_and_count_change_together() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.measure(5, 100);
+
+    v.update_options(|o| {
+        o.window_base = 5;
+        o.count = 11;
+    });
+    assert_eq!(v.item_size(0), Some(100));
+    assert!(v.is_measured(0));
+    assert_eq!(v.key_for(0), 5);
+    assert_eq!(v.total_size(), 100 + 10 * 10);
+}
+
+#[test]
+fn set_options_reclamps_when_size_bounds_and_count_change_together() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.update_options(|o| {
+        o.count = 11;
+        o.min_item_size = 20;
+    });
+    assert_eq!(v.total_size(), 220);
+    assert_eq!(v.item_size(0), Some(20));
+
+    let fresh =
+        Virtualizer::new(VirtualizerOptions::new(11, |_| 10).with_item_size_bounds(20, None));
+    assert_eq!(v.total_size(), fresh.total_size());
+}
+
+#[test]
+fn set_options_rebuilds_when_pins_and_count_change_together() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.update_options(|o| {
+        o.count = 11;
+        o.always_pinned = vec![0];
+    });
+    let fresh = Virtualizer::new(VirtualizerOptions::new(11, |_| 10).with_always_pinned(vec![0]));
+    assert_eq!(v.total_size(), 100);
+    assert_eq!(v.total_size(), fresh.total_size());
+    assert_eq!(v.item_size(0), Some(0));
+}
+
+#[test]
+fn set_options_rebuilds_gaps_when_gap_and_count_change_together() {
+    let mut v = Virtualizer::new(VirtualizerOptions::new(10, |_| 10));
+    v.update_options(|o| {
+        o.count = 20;
+        o.gap = 5;
+    });
+    let fresh = Virtualizer::new(VirtualizerOptions::new(20, |_| 10).with_gap(5));
+    assert_eq!(v.total_size(), 295);
+    assert_eq!(v.total_size(), fresh.total_size());
+    assert_eq!(v.item_start(5), fresh.item_start(5));
+
+    // Shrinking while dropping the gap behaves the same way.
+    let change = v.update_options(|o| {
+        o.count = 8;
+        o.gap = 0;
+    });
+    assert!(change.rebuilt_fenwick);
+    assert_eq!(v.total_size(), 80);
+    assert_eq!(v.item_start(7), Some(70));
+}
+
+#[test]
+fn new_normalizes_an_unsorted_always_pinned_field() {
+    let mut options = VirtualizerOptions::new(20, |_| 10).with_initial_rect(Some(Rect {
+        main: 200,
+        cross: 0,
+    }));
+    options.always_pinned = vec![9, 1, 5, 3, 5];
+    let v = Virtualizer::new(options);
+    assert_eq!(v.options().always_pinned, [1, 3, 5, 9]);
+    for i in [1, 3, 5, 9] {
+        assert!(v.is_always_pinned(i));
+    }
+    assert_eq!(v.total_size(), 160);
+    let mut indexes = Vec::new();
+    v.for_each_virtual_index(|i| indexes.push(i));
+    assert_eq!(indexes.len(), 16);
+    assert!(indexes.iter().all(|i| ![1, 3, 5, 9].contains(i)));
+}
+
+#[test]
+fn always_pinned_items_are_skipped_by_runs_and_adjusted_by_overlay_size() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(100, |_| 10)
+            .with_always_pinned(vec![0, 3])
+            .with_overscan(1)
+            .with_initial_rect(Some(Rect { main: 50, cross: 0 })),
+    );
+    let mut runs = Vec::new();
+    v.for_each_virtual_run(|r| runs.push((r.start_index, r.end_index)));
+    let mut indexes = Vec::new();
+    v.for_each_virtual_index(|i| indexes.push(i));
+    assert_eq!(runs, [(1, 3), (4, 8)]);
+    assert_eq!(indexes, [1, 2, 4, 5, 6, 7]);
+
+    // Adjusting a pinned item works from its overlay size, not its 0 layout size.
+    v.measure(0, 30);
+    let total = v.total_size();
+    v.adjust_item_size(0, 5);
+    let mut pinned = Vec::new();
+    v.for_each_pinned_item(|it| pinned.push((it.index, it.size)));
+    assert_eq!(pinned, [(0, 35), (3, 10)]);
+    assert_eq!(v.total_size(), total);
+}
+
+#[test]
+fn outlier_mean_ignores_identical_remeasures_and_pinned_items() {
+    let mut v = Virtualizer::new(
+        VirtualizerOptions::new(20, |_| 20)
+            .with_outlier_factor(Some(4.0))
+            .with_always_pinned(vec![19]),
+    );
+    for i in 0..10 {
+        assert!(v.measure(i, 21 + i as u32));
+    }
+    // Mean 25.5 => threshold 102. Re-measuring the same rows every frame (and measuring a
+    // pinned overlay) must not drag the mean towards those sizes.
+    for _ in 0..1_000 {
+        v.measure(0, 21);
+        v.apply_measurements(&[(1, 22)]);
+        v.measure(19, 21);
+    }
+    assert_eq!(v.rejected_measurements(), 0);
+    assert!(v.measure(12, 100));
+    assert_eq!(v.rejected_measurements(), 0);
+    assert!(!v.measure(13, 200));
+    assert_eq!(v.rejected_measurements(), 1);
+}
+
+#[test]
+fn outlier_mean_samples_first_measurements_that_match_the_estimate() {
+    let mut v =
+        Virtualizer::new(VirtualizerOptions::new(100, |_| 20).with_outlier_factor(Some(4.0)));
+    for i in 0..50 {
+        v.measure(i, 20);
+    }
+    assert!(!v.measure(60, 2_000));
+    assert_eq!(v.item_size(60), Some(20));
+    assert_eq!(v.rejected_measurements(), 1);
+
+    // Exact-match first measurements via `apply_measurements` establish the baseline too.
+    let mut v =
+        Virtualizer::new(VirtualizerOptions::new(100, |_| 20).with_outlier_factor(Some(4.0)));
+    let batch: Vec<_> = (0..50).map(|i| (i, 20)).collect();
+    assert!(!v.apply_measurements(&batch));
+    assert!(!v.measure(60, 2_000));
+    assert_eq!(v.rejected_measurements(), 1);
+}
@@ -1,5 +1,7 @@
 /// Alignment used by scroll-to helpers.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// This is `PartialEq` but not `Eq`, since [`Align::Fraction`] carries an `f32`.
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
     /// Align the item start to the viewport start.
@@ -10,6 +12,85 @@ pub enum Align {
     End,
     /// Choose `Start`/`End` automatically based on visibility.
     Auto,
+    /// Keep the current offset if the item is fully visible; otherwise center it.
+    CenterIfNeeded,
+    /// Scroll by the minimal distance that makes the item fully visible (respecting scroll
+    /// padding); keep the current offset if it already is.
+    ///
+    /// Items taller than the viewport keep the current offset if they already fill the viewport
+    /// and are otherwise aligned to the start (`Auto` aligns the end of an item below the
+    /// viewport, hiding the start of an over-tall item).
+    Nearest,
+    /// Place the item at a fraction of the way down the viewport (e.g. `0.3` for a "focus
+    /// mode" line), clamped to `[0, 1]`; NaN is treated as `0`.
+    ///
+    /// The item's position is interpolated so that `0.0` matches `Start` and `1.0` matches
+    /// `End` (including scroll padding): its top lands at `fraction` of the free space above it.
+    Fraction(f32),
+}
+
+/// Rounding applied when `Align::Center` lands on a half pixel (odd `item size - viewport`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// Round half-pixel targets up (towards the end of the list).
+    #[default]
+    HalfUp,
+    /// Round half-pixel targets down (towards the start of the list).
+    HalfDown,
+}
+
+/// Why per-index layout state was rebuilt (see `VirtualizerOptions::on_structural_change`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StructuralChange {
+    /// `count` changed; indexes past the new count are gone (or new ones appeared).
+    Count,
+    /// Per-index sizes were rebuilt from estimates and the measurement cache (estimate, key
+    /// mapping, window or size bound changes, `sync_item_keys`, cache import/reset).
+    Estimates,
+    /// Only the prefix sums were rebuilt (e.g. `gap` changed); sizes are unchanged, offsets moved.
+    Layout,
+}
+
+/// Why a virtualizer state change notification fired.
+///
+/// Variants are ordered from least to most significant. When updates are coalesced (e.g. via
+/// `batch_update`), the most significant reason is reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChangeReason {
+    /// Only the `is_scrolling` flag changed (no layout change).
+    ScrollingFlag,
+    /// The scroll offset changed.
+    Scroll,
+    /// The viewport size / scroll rect changed.
+    Resize,
+    /// Item measurements changed.
+    Measure,
+    /// The item count (or window) changed.
+    Count,
+    /// Options or callbacks changed.
+    Options,
+}
+
+/// What `Virtualizer::set_options` rebuilt while applying new options.
+///
+/// Adapters can use this to invalidate only the caches that are affected (e.g. clear a widget
+/// pool only when `rebuilt_estimates` is set). All fields `false` means only cheap fields (e.g.
+/// `overscan`, callbacks) changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionsChange {
+    /// Per-index sizes were rebuilt or resized from estimates and the measurement cache
+    /// (including `count` changes).
+    pub rebuilt_estimates: bool,
+    /// Only the prefix sums were rebuilt (e.g. `gap` changed); sizes are unchanged.
+    pub rebuilt_fenwick: bool,
+    /// Viewport/scroll state was reset (the virtualizer was disabled or re-enabled).
+    pub reset: bool,
+    /// `count` changed.
+    pub count_changed: bool,
 }
 
 /// Scroll direction derived from the latest scroll offset update.
@@ -22,6 +103,39 @@ pub enum ScrollDirection {
     Backward,
 }
 
+/// Coarse virtualizer state, see `Virtualizer::status`.
+///
+/// Query methods return the same empty results for `Disabled` and `Empty`; this lets adapters
+/// tell "virtualization is off, render everything" apart from "nothing to show".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VirtualizerStatus {
+    /// `enabled == false`.
+    Disabled,
+    /// Enabled, but `count == 0` or the viewport size is 0.
+    Empty,
+    /// Enabled with items and a non-zero viewport.
+    Active,
+}
+
+/// Classification of a scroll container offset (see `Virtualizer::hit_region`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HitRegion {
+    /// Before the list (inside a positive `scroll_margin`).
+    BeforeList,
+    /// Inside `padding_start` (or `leading_gap`).
+    PaddingStart,
+    /// On the item with this index.
+    Item(usize),
+    /// In the gap after the item with this index.
+    Gap(usize),
+    /// Inside `trailing_gap` (or `padding_end`).
+    PaddingEnd,
+    /// Past the end of the list.
+    AfterList,
+}
+
 /// A platform-agnostic viewport rect.
 ///
 /// - `main`: size of the scroll axis (height for vertical lists, width for horizontal lists).
@@ -33,6 +147,62 @@ pub struct Rect {
     pub cross: u32,
 }
 
+/// A rendered window over a uniform layout (see `Virtualizer::uniform_window`).
+///
+/// Item `i` in `[start_index, end_index)` starts at
+/// `first_start + (i - start_index) * (item_size + gap)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniformWindow {
+    pub start_index: usize,
+    pub end_index: usize, // exclusive
+    pub item_size: u32,
+    /// Start of `start_index` (includes `scroll_margin` and `padding_start`, like
+    /// [`VirtualItem::start`]).
+    pub first_start: u64,
+    pub gap: u32,
+}
+
+impl UniformWindow {
+    /// Returns the start of `index` (which should lie in `[start_index, end_index)`).
+    pub fn item_start(&self, index: usize) -> u64 {
+        let stride = self.item_size as u64 + self.gap as u64;
+        self.first_start
+            .saturating_add((index.saturating_sub(self.start_index) as u64).saturating_mul(stride))
+    }
+}
+
+/// Read-only view of measured sizes passed to `VirtualizerOptions::estimate_size_ctx`.
+///
+/// Indexes are absolute (the same index the estimate closure receives). Only items known to the
+/// layout so far are visible: while appending, items after the estimated one do not exist yet.
+#[derive(Clone, Copy, Debug)]
+pub struct EstimateCtx<'a> {
+    pub(crate) base: usize,
+    pub(crate) sizes: &'a [u32],
+    pub(crate) measured: &'a [bool],
+}
+
+impl EstimateCtx<'_> {
+    /// Returns `(index, size)` of the nearest measured item before `index`.
+    pub fn nearest_measured_before(&self, index: usize) -> Option<(usize, u32)> {
+        let local = index.checked_sub(self.base)?.min(self.measured.len());
+        let i = self.measured[..local].iter().rposition(|&m| m)?;
+        Some((self.base + i, self.sizes[i]))
+    }
+
+    /// Returns `(index, size)` of the nearest measured item after `index`.
+    pub fn nearest_measured_after(&self, index: usize) -> Option<(usize, u32)> {
+        let from = match index.checked_sub(self.base) {
+            Some(local) => local.saturating_add(1),
+            None => 0,
+        };
+        let rest = self.measured.get(from..)?;
+        let i = from + rest.iter().position(|&m| m)?;
+        Some((self.base + i, self.sizes[i]))
+    }
+}
+
 /// A half-open range of virtual item indexes: `[start_index, end_index)`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -60,6 +230,84 @@ impl VirtualRange {
     pub fn as_inclusive(&self) -> Option<core::ops::RangeInclusive<usize>> {
         Some(self.start_index..=self.end_inclusive()?)
     }
+
+    /// Returns the number of items in the range.
+    pub fn len(&self) -> usize {
+        self.end_index.saturating_sub(self.start_index)
+    }
+
+    /// Returns an iterator over the indexes in `[start_index, end_index)`.
+    ///
+    /// Empty (or inverted) ranges yield nothing. The iterator is double-ended, so
+    /// `range.iter().rev()` walks the range backwards.
+    ///
+    /// ```
+    /// use virtualizer::VirtualRange;
+    ///
+    /// let r = VirtualRange { start_index: 2, end_index: 5 };
+    /// assert_eq!(r.iter().collect::<Vec<_>>(), [2, 3, 4]);
+    /// assert_eq!(r.iter().rev().next(), Some(4));
+    /// ```
+    pub fn iter(&self) -> core::ops::Range<usize> {
+        self.start_index..self.end_index
+    }
+
+    /// Returns `true` if `index` is within `[start_index, end_index)`.
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start_index && index < self.end_index
+    }
+
+    /// Returns the overlap of both ranges.
+    ///
+    /// Empty ranges are absorbing: if either range (or the overlap) is empty, the result is empty.
+    pub fn intersection(&self, other: VirtualRange) -> VirtualRange {
+        let start_index = self.start_index.max(other.start_index);
+        let end_index = self.end_index.min(other.end_index);
+        if self.is_empty() || other.is_empty() || start_index >= end_index {
+            return VirtualRange {
+                start_index: 0,
+                end_index: 0,
+            };
+        }
+        VirtualRange {
+            start_index,
+            end_index,
+        }
+    }
+
+    /// Returns the smallest range covering both ranges (including any hole between them).
+    ///
+    /// Empty ranges are the identity: the other range is returned unchanged.
+    pub fn union_hull(&self, other: VirtualRange) -> VirtualRange {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        VirtualRange {
+            start_index: self.start_index.min(other.start_index),
+            end_index: self.end_index.max(other.end_index),
+        }
+    }
+}
+
+impl IntoIterator for VirtualRange {
+    type Item = usize;
+    type IntoIter = core::ops::Range<usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &VirtualRange {
+    type Item = usize;
+    type IntoIter = core::ops::Range<usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 /// A virtual item produced for rendering.
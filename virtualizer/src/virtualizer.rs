@@ -4,12 +4,13 @@ use core::cell::Cell;
 use core::cmp;
 
 use crate::fenwick::Fenwick;
-use crate::key::{KeyCacheKey, KeySizeMap};
+use crate::key::{KeyCacheKey, KeyIndexMap, KeySizeMap, shrink_key_sizes};
 use crate::{
-    Align, InitialOffset, ItemKey, Range, Rect, ScrollDirection, VirtualItem, VirtualItemKeyed,
-    VirtualRange, VirtualizerOptions,
+    Align, ChangeReason, EstimateCtx, HitRegion, InitialOffset, ItemKey, OptionsChange, Range,
+    Rect, Rounding, ScrollDirection, StructuralChange, UniformWindow, VirtualItem,
+    VirtualItemKeyed, VirtualRange, VirtualizerOptions, VirtualizerStatus,
 };
-use crate::{FrameState, ScrollState, ViewportState};
+use crate::{DebugReport, FrameState, ScrollState, ViewportState};
 
 /// A headless virtualization engine.
 ///
@@ -28,14 +29,32 @@ pub struct Virtualizer<K = ItemKey> {
     is_scrolling: bool,
     scroll_direction: Option<ScrollDirection>,
     last_scroll_event_ms: Option<u64>,
+    last_overshoot: i64,
+    last_scroll_sample: Option<(u64, u64)>,
+    scroll_velocity: f32,
 
     sizes: Vec<u32>, // base sizes (no gap)
     measured: Vec<bool>,
     sums: Fenwick,
     key_sizes: KeySizeMap<K>,
+    key_index: KeyIndexMap<K>,
+    // Lazily computed "all sizes equal" result (`None` = unknown); reset on any size change.
+    uniform_size: Cell<Option<Option<u32>>>,
 
     notify_depth: Cell<usize>,
-    notify_pending: Cell<bool>,
+    notify_pending: Cell<Option<ChangeReason>>,
+    // Set by every notification; cleared by `take_dirty`.
+    dirty: Cell<bool>,
+    extractor_violations: Cell<u64>,
+    // Running mean of accepted measurements (for `outlier_factor`).
+    measured_sum: u64,
+    measured_samples: u64,
+    rejected_measurements: u64,
+    // Optional per-index cross-axis sizes; allocated on the first `measure_cross`.
+    cross_sizes: Vec<u32>,
+    max_cross_size: u32,
+    // `InitialOffset::End` waiting for the first non-zero viewport.
+    pending_scroll_to_end: bool,
 }
 
 impl<K: KeyCacheKey> Virtualizer<K> {
@@ -44,6 +63,50 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     /// If `options.initial_rect` and/or `options.initial_offset` are set, those values are applied
     /// immediately.
     pub fn new(options: VirtualizerOptions<K>) -> Self {
+        let mut v = Self::empty(options);
+        v.rebuild_estimates_quiet();
+        v.arm_scroll_to_end();
+        v
+    }
+
+    /// Creates a new virtualizer with internal buffers pre-sized for `capacity` items.
+    ///
+    /// `count` stays at `options.count`; growing it up to `capacity` (via `set_count`) does not
+    /// reallocate the per-index buffers.
+    pub fn with_capacity(options: VirtualizerOptions<K>, capacity: usize) -> Self {
+        let mut v = Self::new(options);
+        let additional = capacity.saturating_sub(v.options.count);
+        v.sizes.reserve_exact(additional);
+        v.measured.reserve_exact(additional);
+        v.sums.reserve_exact(additional);
+        v
+    }
+
+    /// Creates a virtualizer from a known slice of exact item sizes.
+    ///
+    /// `options.count` is replaced by `sizes.len()`. All items are marked as measured and the
+    /// prefix sums are built directly from `sizes` (the `estimate_size` closure is not called).
+    ///
+    /// The key-based measurement cache starts empty: operations that rebuild from estimates (e.g.
+    /// `set_get_item_key`, `reset_measurements`) fall back to `estimate_size`. Pair this with
+    /// [`VirtualizerOptions::from_sizes`] if estimates should match the exact sizes, or seed the
+    /// cache via `import_measurement_cache`.
+    pub fn from_exact_sizes(mut options: VirtualizerOptions<K>, sizes: &[u32]) -> Self {
+        options.count = sizes.len();
+        let mut v = Self::empty(options);
+        v.sizes = sizes.iter().map(|&size| v.clamp_size(size)).collect();
+        v.measured = alloc::vec![true; sizes.len()];
+        v.zero_pinned_sizes();
+        v.rebuild_fenwick();
+        v.rebuild_key_index();
+        v.arm_scroll_to_end();
+        v
+    }
+
+    fn empty(mut options: VirtualizerOptions<K>) -> Self {
+        // `is_always_pinned` binary-searches, and the pub field may have been set unsorted.
+        options.always_pinned.sort_unstable();
+        options.always_pinned.dedup();
         let scroll_rect = options.initial_rect.unwrap_or_default();
         let scroll_offset = options.initial_offset.resolve();
         vdebug!(
@@ -52,23 +115,34 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             overscan = options.overscan,
             "Virtualizer::new"
         );
-        let mut v = Self {
+        Self {
             viewport_size: scroll_rect.main,
             scroll_offset,
             scroll_rect,
             is_scrolling: false,
             scroll_direction: None,
             last_scroll_event_ms: None,
+            last_overshoot: 0,
+            last_scroll_sample: None,
+            scroll_velocity: 0.0,
             sizes: Vec::new(),
             measured: Vec::new(),
             sums: Fenwick::new(0),
             key_sizes: KeySizeMap::<K>::new(),
+            key_index: KeyIndexMap::<K>::new(),
+            uniform_size: Cell::new(None),
             options,
             notify_depth: Cell::new(0),
-            notify_pending: Cell::new(false),
-        };
-        v.rebuild_estimates();
-        v
+            notify_pending: Cell::new(None),
+            dirty: Cell::new(false),
+            extractor_violations: Cell::new(0),
+            measured_sum: 0,
+            measured_samples: 0,
+            rejected_measurements: 0,
+            cross_sizes: Vec::new(),
+            max_cross_size: 0,
+            pending_scroll_to_end: false,
+        }
     }
 
     pub fn options(&self) -> &VirtualizerOptions<K> {
@@ -82,16 +156,66 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         self.is_scrolling = false;
         self.scroll_direction = None;
         self.last_scroll_event_ms = None;
+        self.last_overshoot = 0;
+        self.last_scroll_sample = None;
+        self.scroll_velocity = 0.0;
+        self.arm_scroll_to_end();
+    }
+
+    /// Re-arms `InitialOffset::End`, resolving it right away if the viewport is already known.
+    fn arm_scroll_to_end(&mut self) {
+        self.pending_scroll_to_end = matches!(self.options.initial_offset, InitialOffset::End);
+        self.resolve_pending_scroll_to_end();
+    }
+
+    fn resolve_pending_scroll_to_end(&mut self) {
+        if self.pending_scroll_to_end && self.options.enabled && self.viewport_size > 0 {
+            self.pending_scroll_to_end = false;
+            self.scroll_offset = self.max_scroll_offset();
+        }
     }
 
-    pub fn set_options(&mut self, options: VirtualizerOptions<K>) {
+    /// Replaces the options, rebuilding only what the change requires.
+    ///
+    /// Returns what was rebuilt (see [`OptionsChange`]); the value can be ignored. Identical
+    /// options (equal data, same closure `Arc`s) are a no-op and do not notify.
+    pub fn set_options(&mut self, mut options: VirtualizerOptions<K>) -> OptionsChange {
+        options.always_pinned.sort_unstable();
+        options.always_pinned.dedup();
+        if self.options.same_as(&options) {
+            return OptionsChange::default();
+        }
+        let always_pinned_unchanged = self.options.always_pinned == options.always_pinned;
         let prev_count = self.options.count;
         let prev_gap = self.options.gap;
+        let prev_window_base = self.options.window_base;
+        let prev_size_bounds = (self.options.min_item_size, self.options.max_item_size);
+        let gap_after_unchanged = match (&self.options.gap_after, &options.gap_after) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
         let was_enabled = self.options.enabled;
+        let prev_maintain_key_index = self.options.maintain_key_index;
         let estimate_size_unchanged =
-            Arc::ptr_eq(&self.options.estimate_size, &options.estimate_size);
+            Arc::ptr_eq(&self.options.estimate_size, &options.estimate_size)
+                && self.options.uniform_estimate == options.uniform_estimate
+                && match (&self.options.estimate_size_ctx, &options.estimate_size_ctx) {
+                    (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                    (None, None) => true,
+                    _ => false,
+                };
         let get_item_key_unchanged = Arc::ptr_eq(&self.options.get_item_key, &options.get_item_key);
+        let range_extractor_unchanged =
+            match (&self.options.range_extractor, &options.range_extractor) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            };
         self.options = options;
+        if !range_extractor_unchanged {
+            self.extractor_violations.set(0);
+        }
         vtrace!(
             count = self.options.count,
             enabled = self.options.enabled,
@@ -99,6 +223,15 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             "Virtualizer::set_options"
         );
 
+        let sizes_unchanged = estimate_size_unchanged
+            && get_item_key_unchanged
+            && always_pinned_unchanged
+            && self.options.window_base == prev_window_base
+            && (self.options.min_item_size, self.options.max_item_size) == prev_size_bounds;
+        let mut change = OptionsChange {
+            count_changed: self.options.count != prev_count,
+            ..OptionsChange::default()
+        };
         if !self.options.enabled {
             self.viewport_size = 0;
             self.scroll_offset = self.options.initial_offset.resolve();
@@ -106,31 +239,58 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             self.is_scrolling = false;
             self.scroll_direction = None;
             self.last_scroll_event_ms = None;
+            self.last_overshoot = 0;
+            self.last_scroll_sample = None;
+            self.scroll_velocity = 0.0;
+            change.reset = true;
         } else if !was_enabled {
             self.reset_to_initial();
+            change.reset = true;
         } else if self.options.count != prev_count {
-            if estimate_size_unchanged && get_item_key_unchanged {
-                self.resize_count(prev_count, self.options.count);
+            // Growing/shrinking in place is only valid if nothing else that affects the
+            // per-item sizes changed in the same update.
+            if sizes_unchanged {
+                self.resize_count_quiet(prev_count, self.options.count);
+                // The kept prefix sums still carry the previous gaps.
+                if self.options.gap != prev_gap || !gap_after_unchanged {
+                    self.rebuild_fenwick();
+                    change.rebuilt_fenwick = true;
+                }
+                self.notify_structural(StructuralChange::Count);
             } else {
-                self.rebuild_estimates();
+                self.rebuild_estimates_quiet();
+                self.notify_structural(StructuralChange::Count);
             }
-        } else if !estimate_size_unchanged || !get_item_key_unchanged {
+            change.rebuilt_estimates = true;
+        } else if !sizes_unchanged {
             self.rebuild_estimates();
-        } else if self.options.gap != prev_gap {
+            change.rebuilt_estimates = true;
+        } else if self.options.gap != prev_gap || !gap_after_unchanged {
             self.rebuild_fenwick();
+            self.notify_structural(StructuralChange::Layout);
+            change.rebuilt_fenwick = true;
+        }
+        if self.options.maintain_key_index != prev_maintain_key_index
+            || (!change.rebuilt_estimates
+                && (change.count_changed
+                    || !get_item_key_unchanged
+                    || self.options.window_base != prev_window_base))
+        {
+            self.rebuild_key_index();
         }
 
-        self.notify();
+        self.notify(ChangeReason::Options);
+        change
     }
 
     /// Clones the current options, applies `f`, then delegates to `set_options`.
     ///
     /// This is useful when you want to update multiple options at once while letting the
     /// virtualizer decide what needs to be rebuilt (estimates/fenwick/reset).
-    pub fn update_options(&mut self, f: impl FnOnce(&mut VirtualizerOptions<K>)) {
+    pub fn update_options(&mut self, f: impl FnOnce(&mut VirtualizerOptions<K>)) -> OptionsChange {
         let mut next = self.options.clone();
         f(&mut next);
-        self.set_options(next);
+        self.set_options(next)
     }
 
     pub fn set_on_change(
@@ -138,12 +298,31 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         on_change: Option<impl Fn(&Virtualizer<K>, bool) + Send + Sync + 'static>,
     ) {
         self.options.on_change = on_change.map(|f| Arc::new(f) as _);
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_on_change_with_reason(
+        &mut self,
+        on_change: Option<impl Fn(&Virtualizer<K>, ChangeReason) + Send + Sync + 'static>,
+    ) {
+        self.options.on_change_with_reason = on_change.map(|f| Arc::new(f) as _);
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_on_structural_change(
+        &mut self,
+        on_structural_change: Option<impl Fn(StructuralChange) + Send + Sync + 'static>,
+    ) {
+        self.options.on_structural_change = on_structural_change.map(|f| Arc::new(f) as _);
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_initial_offset(&mut self, initial_offset: u64) {
+        if matches!(self.options.initial_offset, InitialOffset::Value(v) if v == initial_offset) {
+            return;
+        }
         self.options.initial_offset = InitialOffset::Value(initial_offset);
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_initial_offset_provider(
@@ -151,52 +330,99 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         initial_offset: impl Fn() -> u64 + Send + Sync + 'static,
     ) {
         self.options.initial_offset = InitialOffset::Provider(Arc::new(initial_offset));
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_use_scrollend_event(&mut self, use_scrollend_event: bool) {
+        if self.options.use_scrollend_event == use_scrollend_event {
+            return;
+        }
         self.options.use_scrollend_event = use_scrollend_event;
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_is_scrolling_reset_delay_ms(&mut self, delay_ms: u64) {
+        if self.options.is_scrolling_reset_delay_ms == delay_ms {
+            return;
+        }
         self.options.is_scrolling_reset_delay_ms = delay_ms;
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    fn notify_structural(&self, change: StructuralChange) {
+        if let Some(f) = &self.options.on_structural_change {
+            f(change);
+        }
     }
 
-    fn notify_now(&self) {
+    fn notify_now(&self, reason: ChangeReason) {
         if let Some(cb) = &self.options.on_change {
             cb(self, self.is_scrolling);
         }
+        if let Some(cb) = &self.options.on_change_with_reason {
+            cb(self, reason);
+        }
     }
 
-    fn notify(&self) {
+    fn notify(&self, reason: ChangeReason) {
+        self.dirty.set(true);
         if self.notify_depth.get() > 0 {
-            self.notify_pending.set(true);
+            let pending = self.notify_pending.get().map_or(reason, |p| p.max(reason));
+            self.notify_pending.set(Some(pending));
             return;
         }
-        self.notify_now();
+        self.notify_now(reason);
+    }
+
+    /// Returns whether any state changed since the previous call, and clears the flag.
+    ///
+    /// This is the pull-based alternative to `on_change` for immediate-mode UIs: poll it once
+    /// per frame instead of reacting to callbacks. It is set by every update that would notify
+    /// `on_change` (including updates inside `batch_update`), whether or not a callback is
+    /// installed; setters that don't change anything leave it untouched.
+    pub fn take_dirty(&mut self) -> bool {
+        self.dirty.replace(false)
     }
 
     /// Batches multiple updates into a single `on_change` notification.
     ///
+    /// The `on_change_with_reason` callback receives the most significant reason among the
+    /// coalesced updates (see [`ChangeReason`]).
+    ///
     /// This is recommended for UI adapters: on a typical frame, you might update the scroll
     /// rect, scroll offset, and `is_scrolling` state together. Without batching, each setter may
     /// trigger `on_change`, which can be expensive if the callback drives rendering.
     pub fn batch_update(&mut self, f: impl FnOnce(&mut Self)) {
+        self.with_suspended_notifications(f);
+    }
+
+    /// Runs `f` with `on_change`/`on_change_with_reason` suspended and returns its result.
+    ///
+    /// Guarantees that neither callback is invoked while `f` runs (including from nested
+    /// `batch_update` calls), so callbacks never observe half-applied restructurings such as
+    /// `set_count` followed by `set_get_item_key`. When the outermost block ends, the callbacks
+    /// fire exactly once with the most significant [`ChangeReason`] if anything changed, and not
+    /// at all otherwise.
+    ///
+    /// Other hooks that run synchronously as part of an update (`estimate_size`,
+    /// `get_item_key`, `should_adjust_scroll_position_on_item_size_change`) are unaffected.
+    pub fn with_suspended_notifications<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
         let depth = self.notify_depth.get();
         self.notify_depth.set(depth.saturating_add(1));
 
-        f(self);
+        let out = f(self);
 
         let depth = self.notify_depth.get();
         debug_assert!(depth > 0, "notify_depth underflow");
         let next = depth.saturating_sub(1);
         self.notify_depth.set(next);
 
-        if next == 0 && self.notify_pending.replace(false) {
-            self.notify_now();
+        if next == 0
+            && let Some(reason) = self.notify_pending.take()
+        {
+            self.notify_now(reason);
         }
+        out
     }
 
     pub fn count(&self) -> usize {
@@ -207,6 +433,21 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         self.options.enabled
     }
 
+    /// Returns whether the virtualizer is disabled, empty (no items or a zero-sized viewport) or
+    /// active.
+    ///
+    /// Use this to pick a non-virtualized render path when disabled; `virtual_range` and friends
+    /// return empty results in both the `Disabled` and `Empty` states.
+    pub fn status(&self) -> VirtualizerStatus {
+        if !self.options.enabled {
+            VirtualizerStatus::Disabled
+        } else if self.options.count == 0 || self.viewport_size == 0 {
+            VirtualizerStatus::Empty
+        } else {
+            VirtualizerStatus::Active
+        }
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
         if self.options.enabled == enabled {
             return;
@@ -219,10 +460,13 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             self.is_scrolling = false;
             self.scroll_direction = None;
             self.last_scroll_event_ms = None;
+            self.last_overshoot = 0;
+            self.last_scroll_sample = None;
+            self.scroll_velocity = 0.0;
         } else {
             self.reset_to_initial();
         }
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
     pub fn is_scrolling(&self) -> bool {
@@ -241,8 +485,11 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         if !is_scrolling {
             self.scroll_direction = None;
             self.last_scroll_event_ms = None;
+            self.last_overshoot = 0;
+            self.last_scroll_sample = None;
+            self.scroll_velocity = 0.0;
         }
-        self.notify();
+        self.notify(ChangeReason::ScrollingFlag);
     }
 
     pub fn notify_scroll_event(&mut self, now_ms: u64) {
@@ -302,6 +549,24 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         }
     }
 
+    /// Returns a diagnostic snapshot of derived state, suitable for attaching to bug reports.
+    ///
+    /// This runs in `O(n)` (it counts measured items); avoid calling it per frame.
+    pub fn debug_report(&self) -> DebugReport {
+        DebugReport {
+            count: self.options.count,
+            enabled: self.options.enabled,
+            viewport_size: self.viewport_size,
+            scroll_offset: self.scroll_offset,
+            max_scroll_offset: self.max_scroll_offset(),
+            total_size: self.total_size(),
+            visible_range: self.visible_range(),
+            virtual_range: self.virtual_range(),
+            measured_count: self.measured.iter().filter(|&&m| m).count(),
+            scroll_direction: self.scroll_direction,
+        }
+    }
+
     /// Restores viewport geometry from a previously captured snapshot.
     pub fn restore_viewport_state(&mut self, viewport: ViewportState) {
         self.set_scroll_rect(viewport.rect);
@@ -344,7 +609,8 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         }
         self.scroll_rect = rect;
         self.viewport_size = rect.main;
-        self.notify();
+        self.resolve_pending_scroll_to_end();
+        self.notify(ChangeReason::Resize);
     }
 
     /// Applies a scroll rect update from your UI layer.
@@ -362,8 +628,31 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     }
 
     pub fn scroll_offset_in_list(&self) -> u64 {
-        let margin = self.options.scroll_margin as u64;
-        self.scroll_offset.saturating_sub(margin)
+        self.container_to_list(self.scroll_offset)
+    }
+
+    /// Maps a scroll container offset to list coordinates by removing the signed `scroll_margin`
+    /// (saturating at 0).
+    ///
+    /// List coordinates start at the top of the list, so they still include `padding_start`.
+    pub fn container_offset_to_list(&self, offset: u64) -> u64 {
+        self.container_to_list(offset)
+    }
+
+    /// Maps a list offset to scroll container coordinates by adding the signed `scroll_margin`
+    /// (saturating at 0). Inverse of [`Self::container_offset_to_list`] for offsets not clipped
+    /// by the saturation.
+    pub fn list_offset_to_container(&self, offset: u64) -> u64 {
+        self.list_to_container(offset)
+    }
+
+    /// Returns the start of `index` in list coordinates: like [`Self::item_start`], but without
+    /// `scroll_margin`. Returns `None` when disabled or out of bounds.
+    pub fn item_start_in_list(&self, index: usize) -> Option<u64> {
+        if !self.options.enabled {
+            return None;
+        }
+        (index < self.options.count).then(|| self.start_of(index))
     }
 
     pub fn set_viewport_size(&mut self, size: u32) {
@@ -372,10 +661,24 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         }
         self.viewport_size = size;
         self.scroll_rect.main = size;
-        self.notify();
+        self.resolve_pending_scroll_to_end();
+        self.notify(ChangeReason::Resize);
     }
 
     pub fn set_scroll_offset(&mut self, offset: u64) {
+        let offset = match self.options.scroll_grid {
+            Some(grid) if grid > 1 => {
+                // Never round an in-range offset up past the end of the content.
+                let snapped = self.snap_offset_to_grid(offset, grid);
+                let max = self.max_scroll_offset();
+                if snapped > max && offset <= max {
+                    snapped - grid as u64
+                } else {
+                    snapped
+                }
+            }
+            _ => offset,
+        };
         if self.scroll_offset == offset {
             return;
         }
@@ -386,7 +689,7 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             cmp::Ordering::Less => Some(ScrollDirection::Backward),
             cmp::Ordering::Equal => self.scroll_direction,
         };
-        self.notify();
+        self.notify(ChangeReason::Scroll);
     }
 
     /// Applies a scroll offset update from your UI layer (e.g. wheel/drag), and marks the
@@ -394,11 +697,28 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     pub fn apply_scroll_offset_event(&mut self, offset: u64, now_ms: u64) {
         vtrace!(offset, now_ms, "apply_scroll_offset_event");
         self.batch_update(|v| {
+            v.record_overshoot(offset);
             v.set_scroll_offset(offset);
+            v.record_scroll_sample(now_ms);
             v.notify_scroll_event(now_ms);
         });
     }
 
+    /// Same as [`Self::set_scroll_offset`], for UI toolkits with floating-point scroll positions.
+    ///
+    /// The offset is rounded half-to-even (`10.5 -> 10`, `11.5 -> 12`, `10.6 -> 11`); negative
+    /// and NaN offsets become 0. Routing every float through here keeps rounding consistent
+    /// between frames, avoiding 1px jitter.
+    pub fn set_scroll_offset_f64(&mut self, offset: f64) {
+        self.set_scroll_offset(round_offset(offset));
+    }
+
+    /// Same as [`Self::apply_scroll_offset_event`], with the offset rounded like
+    /// [`Self::set_scroll_offset_f64`].
+    pub fn apply_scroll_offset_event_f64(&mut self, offset: f64, now_ms: u64) {
+        self.apply_scroll_offset_event(round_offset(offset), now_ms);
+    }
+
     pub fn set_scroll_offset_clamped(&mut self, offset: u64) {
         let clamped = self.clamp_scroll_offset(offset);
         self.set_scroll_offset(clamped);
@@ -408,7 +728,9 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     pub fn apply_scroll_offset_event_clamped(&mut self, offset: u64, now_ms: u64) {
         vtrace!(offset, now_ms, "apply_scroll_offset_event_clamped");
         self.batch_update(|v| {
+            v.record_overshoot(offset);
             v.set_scroll_offset_clamped(offset);
+            v.record_scroll_sample(now_ms);
             v.notify_scroll_event(now_ms);
         });
     }
@@ -441,7 +763,9 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         );
         self.batch_update(|v| {
             v.set_scroll_rect(rect);
+            v.record_overshoot(scroll_offset);
             v.set_scroll_offset(scroll_offset);
+            v.record_scroll_sample(now_ms);
             v.notify_scroll_event(now_ms);
         });
     }
@@ -457,7 +781,9 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         );
         self.batch_update(|v| {
             v.set_scroll_rect(rect);
+            v.record_overshoot(scroll_offset);
             v.set_scroll_offset_clamped(scroll_offset);
+            v.record_scroll_sample(now_ms);
             v.notify_scroll_event(now_ms);
         });
     }
@@ -469,29 +795,151 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         let prev = self.options.count;
         self.options.count = count;
         self.resize_count(prev, count);
-        self.notify();
+        self.notify(ChangeReason::Count);
     }
 
     pub fn set_overscan(&mut self, overscan: usize) {
+        if self.options.overscan == overscan {
+            return;
+        }
         self.options.overscan = overscan;
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Sets (or clears) the pixel overscan (see [`VirtualizerOptions::overscan_px`]).
+    pub fn set_overscan_px(&mut self, overscan_px: Option<u32>) {
+        if self.options.overscan_px == overscan_px {
+            return;
+        }
+        self.options.overscan_px = overscan_px;
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_placeholder_overscan(&mut self, placeholder_overscan: usize) {
+        if self.options.placeholder_overscan == placeholder_overscan {
+            return;
+        }
+        self.options.placeholder_overscan = placeholder_overscan;
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Sets [`VirtualizerOptions::always_pinned`] (sorted and deduplicated) and rebuilds sizes.
+    pub fn set_always_pinned(&mut self, mut always_pinned: Vec<usize>) {
+        always_pinned.sort_unstable();
+        always_pinned.dedup();
+        if self.options.always_pinned == always_pinned {
+            return;
+        }
+        self.options.always_pinned = always_pinned;
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Returns `true` if `index` is listed in [`VirtualizerOptions::always_pinned`].
+    pub fn is_always_pinned(&self, index: usize) -> bool {
+        !self.options.always_pinned.is_empty()
+            && self.options.always_pinned.binary_search(&index).is_ok()
+    }
+
+    /// Iterates the [`VirtualizerOptions::always_pinned`] items as overlays, in index order.
+    ///
+    /// Pinned items are stacked from the top of the viewport: the first one starts at
+    /// `scroll_offset`, each following one right after the previous. Sizes come from the
+    /// measurement cache, falling back to `estimate_size`. Out-of-bounds indexes are skipped.
+    pub fn for_each_pinned_item(&self, mut f: impl FnMut(VirtualItem)) {
+        if !self.options.enabled {
+            return;
+        }
+        let mut start = self.scroll_offset;
+        for &index in &self.options.always_pinned {
+            if index >= self.options.count {
+                break;
+            }
+            let size = self.pinned_size(index);
+            f(VirtualItem { index, start, size });
+            start = start.saturating_add(size as u64);
+        }
+    }
+
+    fn pinned_size(&self, index: usize) -> u32 {
+        match self.key_sizes.get(&self.key_for(index)) {
+            Some(&size) => self.clamp_size(size),
+            None => self.estimate_for(index),
+        }
     }
 
     pub fn set_padding(&mut self, padding_start: u32, padding_end: u32) {
+        if (self.options.padding_start, self.options.padding_end) == (padding_start, padding_end) {
+            return;
+        }
         self.options.padding_start = padding_start;
         self.options.padding_end = padding_end;
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Sets the gaps before the first item and after the last item (see
+    /// [`VirtualizerOptions::leading_gap`]).
+    pub fn set_edge_gaps(&mut self, leading_gap: u32, trailing_gap: u32) {
+        if (self.options.leading_gap, self.options.trailing_gap) == (leading_gap, trailing_gap) {
+            return;
+        }
+        self.options.leading_gap = leading_gap;
+        self.options.trailing_gap = trailing_gap;
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_scroll_padding(&mut self, scroll_padding_start: u32, scroll_padding_end: u32) {
+        if (
+            self.options.scroll_padding_start,
+            self.options.scroll_padding_end,
+        ) == (scroll_padding_start, scroll_padding_end)
+        {
+            return;
+        }
         self.options.scroll_padding_start = scroll_padding_start;
         self.options.scroll_padding_end = scroll_padding_end;
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_center_rounding(&mut self, center_rounding: Rounding) {
+        if self.options.center_rounding == center_rounding {
+            return;
+        }
+        self.options.center_rounding = center_rounding;
+        self.notify(ChangeReason::Options);
     }
 
-    pub fn set_scroll_margin(&mut self, scroll_margin: u32) {
+    pub fn set_scroll_margin(&mut self, scroll_margin: i64) {
+        if self.options.scroll_margin == scroll_margin {
+            return;
+        }
         self.options.scroll_margin = scroll_margin;
-        self.notify();
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Sets (or clears) the grid `set_scroll_offset` snaps to (see
+    /// [`VirtualizerOptions::scroll_grid`]). The current offset is left as is.
+    pub fn set_scroll_grid(&mut self, scroll_grid: Option<u32>) {
+        if self.options.scroll_grid == scroll_grid {
+            return;
+        }
+        self.options.scroll_grid = scroll_grid;
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Rounds `offset` to the nearest multiple of `grid` (ties round up), e.g. a device-pixel
+    /// size. A `grid` of `0` or `1` returns `offset` unchanged; results saturate at `u64::MAX`.
+    pub fn snap_offset_to_grid(&self, offset: u64, grid: u32) -> u64 {
+        if grid <= 1 {
+            return offset;
+        }
+        let grid = grid as u64;
+        let down = offset - offset % grid;
+        if offset - down < grid.div_ceil(2) {
+            down
+        } else {
+            down.saturating_add(grid)
+        }
     }
 
     pub fn set_gap(&mut self, gap: u32) {
@@ -500,13 +948,30 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         }
         self.options.gap = gap;
         self.rebuild_fenwick();
-        self.notify();
+        self.notify_structural(StructuralChange::Layout);
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Sets (or clears) the per-item gap (see [`VirtualizerOptions::gap_after`]).
+    pub fn set_gap_after(&mut self, f: Option<impl Fn(usize) -> u32 + Send + Sync + 'static>) {
+        self.options.gap_after = f.map(|f| Arc::new(f) as _);
+        self.rebuild_fenwick();
+        self.notify_structural(StructuralChange::Layout);
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_max_total_size(&mut self, max_total_size: Option<u64>) {
+        if self.options.max_total_size == max_total_size {
+            return;
+        }
+        self.options.max_total_size = max_total_size;
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_get_item_key(&mut self, f: impl Fn(usize) -> K + Send + Sync + 'static) {
         self.options.get_item_key = Arc::new(f);
         self.rebuild_estimates();
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
     pub fn set_should_adjust_scroll_position_on_item_size_change(
@@ -515,31 +980,87 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     ) {
         self.options
             .should_adjust_scroll_position_on_item_size_change = f.map(|f| Arc::new(f) as _);
-        self.notify();
+        self.notify(ChangeReason::Options);
     }
 
-    pub fn sync_item_keys(&mut self) {
-        // Rebuild per-index sizes from the key-based cache and current estimates.
-        // Call this after your data set is reordered/changed while `count` stays the same.
-        let count = self.options.count;
-        self.sizes.clear();
-        self.measured.clear();
-        self.sizes.reserve_exact(count);
-        self.measured.reserve_exact(count);
+    pub fn set_adjust_scroll_on_resize(&mut self, adjust_scroll_on_resize: bool) {
+        if self.options.adjust_scroll_on_resize == adjust_scroll_on_resize {
+            return;
+        }
+        self.options.adjust_scroll_on_resize = adjust_scroll_on_resize;
+        self.notify(ChangeReason::Options);
+    }
 
-        for i in 0..count {
-            let key = self.key_for(i);
-            if let Some(&measured_size) = self.key_sizes.get(&key) {
-                self.sizes.push(measured_size);
-                self.measured.push(true);
-            } else {
-                self.sizes.push((self.options.estimate_size)(i));
-                self.measured.push(false);
-            }
+    /// Enables or disables the internal key → index map (see
+    /// [`VirtualizerOptions::maintain_key_index`]). Enabling builds the map immediately.
+    pub fn set_maintain_key_index(&mut self, maintain_key_index: bool) {
+        if self.options.maintain_key_index == maintain_key_index {
+            return;
         }
+        self.options.maintain_key_index = maintain_key_index;
+        self.rebuild_key_index();
+        self.notify(ChangeReason::Options);
+    }
 
-        self.rebuild_fenwick();
-        self.notify();
+    pub fn set_clamp_scroll_after_measure(&mut self, clamp_scroll_after_measure: bool) {
+        if self.options.clamp_scroll_after_measure == clamp_scroll_after_measure {
+            return;
+        }
+        self.options.clamp_scroll_after_measure = clamp_scroll_after_measure;
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_outlier_factor(&mut self, outlier_factor: Option<f32>) {
+        if self.options.outlier_factor == outlier_factor {
+            return;
+        }
+        self.options.outlier_factor = outlier_factor;
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn set_freeze_measured(&mut self, freeze_measured: bool) {
+        if self.options.freeze_measured == freeze_measured {
+            return;
+        }
+        self.options.freeze_measured = freeze_measured;
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Marks `count` as provisional (see [`VirtualizerOptions::provisional`]).
+    pub fn set_provisional(&mut self, provisional: bool) {
+        if self.options.provisional == provisional {
+            return;
+        }
+        self.options.provisional = provisional;
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Returns `true` while `count` is provisional and measurements are ignored.
+    pub fn is_provisional(&self) -> bool {
+        self.options.provisional
+    }
+
+    /// Updates `min_item_size`/`max_item_size` and rebuilds sizes from estimates and the
+    /// measurement cache with the new bounds applied.
+    pub fn set_item_size_bounds(&mut self, min_item_size: u32, max_item_size: Option<u32>) {
+        if self.options.min_item_size == min_item_size
+            && self.options.max_item_size == max_item_size
+        {
+            return;
+        }
+        self.options.min_item_size = min_item_size;
+        self.options.max_item_size = max_item_size;
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Options);
+    }
+
+    pub fn sync_item_keys(&mut self) {
+        // Rebuild per-index sizes from the key-based cache and current estimates.
+        // Call this after your data set is reordered/changed while `count` stays the same.
+        self.reload_sizes();
+        self.rebuild_key_index();
+        self.notify_structural(StructuralChange::Estimates);
+        self.notify(ChangeReason::Measure);
     }
 
     pub fn set_range_extractor(
@@ -547,98 +1068,315 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         f: Option<impl Fn(Range, &mut dyn FnMut(usize)) + Send + Sync + 'static>,
     ) {
         self.options.range_extractor = f.map(|f| Arc::new(f) as _);
-        self.notify();
+        self.extractor_violations.set(0);
+        self.notify(ChangeReason::Options);
     }
 
-    pub fn set_estimate_size(&mut self, f: impl Fn(usize) -> u32 + Send + Sync + 'static) {
-        self.options.estimate_size = Arc::new(f);
-        self.rebuild_estimates();
-        self.notify();
+    /// Returns the number of out-of-order/out-of-bounds indexes emitted by `range_extractor`.
+    ///
+    /// Violations are only counted when `validate_extractor` is enabled. The counter is reset when
+    /// the extractor is replaced, or explicitly via [`Self::reset_extractor_violations`].
+    pub fn extractor_violations(&self) -> u64 {
+        self.extractor_violations.get()
     }
 
-    pub fn reset_measurements(&mut self) {
-        self.key_sizes.clear();
-        self.rebuild_estimates();
-        self.notify();
+    pub fn reset_extractor_violations(&mut self) {
+        self.extractor_violations.set(0);
     }
 
-    /// Returns the number of cached measured sizes (key → size).
-    pub fn measurement_cache_len(&self) -> usize {
-        self.key_sizes.len()
+    fn record_extractor_violation(&self) {
+        self.extractor_violations
+            .set(self.extractor_violations.get().saturating_add(1));
     }
 
-    /// Iterates over the cached measured sizes (key → size) without allocations.
-    pub fn for_each_cached_size(&self, mut f: impl FnMut(&K, u32)) {
-        for (k, v) in self.key_sizes.iter() {
-            f(k, *v);
-        }
+    /// Returns the number of measurements rejected by `outlier_factor`.
+    ///
+    /// The counter is reset by [`Self::reset_measurements`], or explicitly via
+    /// [`Self::reset_rejected_measurements`].
+    pub fn rejected_measurements(&self) -> u64 {
+        self.rejected_measurements
     }
 
-    /// Exports the cached measured sizes as a `Vec` (useful for persistence).
-    pub fn export_measurement_cache(&self) -> Vec<(K, u32)>
-    where
-        K: Clone,
-    {
-        let mut out = Vec::with_capacity(self.key_sizes.len());
-        self.for_each_cached_size(|k, v| out.push((k.clone(), v)));
-        out
+    pub fn reset_rejected_measurements(&mut self) {
+        self.rejected_measurements = 0;
     }
 
-    /// Replaces the cached measured sizes from an iterator (useful when restoring state).
-    ///
-    /// Note: this rebuilds internal per-index sizes using the current key mapping.
-    pub fn import_measurement_cache(&mut self, entries: impl IntoIterator<Item = (K, u32)>) {
-        self.key_sizes.clear();
-        let mut n = 0usize;
-        for (k, v) in entries {
-            self.key_sizes.insert(k, v);
-            n = n.saturating_add(1);
+    /// Applies the `outlier_factor` guard to a (clamped) measurement. Returns `true` (and counts
+    /// the rejection) if the measurement is rejected.
+    fn reject_outlier(&mut self, size: u32) -> bool {
+        if let Some(factor) = self.options.outlier_factor
+            && self.measured_samples > 0
+        {
+            let mean = self.measured_sum as f64 / self.measured_samples as f64;
+            if size as f64 > mean * factor as f64 {
+                vwarn!(size, mean, "measurement rejected as outlier");
+                self.rejected_measurements = self.rejected_measurements.saturating_add(1);
+                return true;
+            }
         }
-        vdebug!(entries = n, "import_measurement_cache");
-        self.rebuild_estimates();
-        self.notify();
+        false
     }
 
-    /// Marks an item as measured and updates its cached size.
-    ///
-    /// This is aligned with TanStack Virtual's "measure element" behavior: if the measured item is
-    /// before the current scroll offset, the virtualizer may adjust `scroll_offset` to prevent a
-    /// visible "jump".
-    ///
-    /// If you want to update measurements without any scroll adjustment, use
-    /// `measure_unadjusted`.
-    pub fn measure(&mut self, index: usize, size: u32) {
-        if index >= self.options.count {
-            return;
-        }
-        let _ = self.resize_item(index, size);
+    /// Adds a measurement to the running mean used by `outlier_factor`. Callers sample first
+    /// measurements and size changes; identical re-measures and overlay-pinned items are not
+    /// sampled, so they can't skew the mean.
+    fn record_measurement_sample(&mut self, size: u32) {
+        self.measured_sum = self.measured_sum.saturating_add(size as u64);
+        self.measured_samples = self.measured_samples.saturating_add(1);
     }
 
-    /// Same as [`Self::measure`], but uses a precomputed key to avoid recomputing `get_item_key`.
-    pub fn measure_keyed(&mut self, index: usize, key: K, size: u32) {
-        if index >= self.options.count {
-            return;
-        }
-        let _ = self.resize_item_keyed(index, key, size);
+    pub fn set_estimate_size(&mut self, f: impl Fn(usize) -> u32 + Send + Sync + 'static) {
+        self.options.estimate_size = Arc::new(f);
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Options);
     }
 
-    /// Marks an item as measured and updates its cached size without adjusting `scroll_offset`.
-    pub fn measure_unadjusted(&mut self, index: usize, size: u32) {
-        if index >= self.options.count {
+    /// Sets (or clears) the uniform estimate (see [`VirtualizerOptions::uniform_estimate`]) and
+    /// rebuilds estimates.
+    pub fn set_uniform_estimate(&mut self, uniform_estimate: Option<u32>) {
+        if self.options.uniform_estimate == uniform_estimate {
             return;
         }
-        let key = self.key_for(index);
-        self.measure_keyed_unadjusted(index, key, size);
+        self.options.uniform_estimate = uniform_estimate;
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Options);
     }
 
-    /// Same as [`Self::measure_unadjusted`], but uses a precomputed key.
-    pub fn measure_keyed_unadjusted(&mut self, index: usize, key: K, size: u32) {
-        if index >= self.options.count {
-            return;
+    /// Sets (or clears) the context-aware estimate (see
+    /// [`VirtualizerOptions::estimate_size_ctx`]) and rebuilds estimates.
+    pub fn set_estimate_size_ctx(
+        &mut self,
+        f: Option<impl Fn(usize, &EstimateCtx<'_>) -> u32 + Send + Sync + 'static>,
+    ) {
+        self.options.estimate_size_ctx = f.map(|f| Arc::new(f) as _);
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Options);
+    }
+
+    /// Same as [`Self::set_estimate_size`], but only re-estimates unmeasured items.
+    ///
+    /// See [`Self::refresh_estimates_incremental`].
+    pub fn set_estimate_size_incremental(
+        &mut self,
+        f: impl Fn(usize) -> u32 + Send + Sync + 'static,
+    ) {
+        self.options.estimate_size = Arc::new(f);
+        self.refresh_estimates_incremental();
+    }
+
+    /// Re-evaluates `estimate_size` for unmeasured items and updates prefix sums in place.
+    ///
+    /// Unlike `set_estimate_size` (which rebuilds every index from the key cache), this skips
+    /// measured items entirely and never calls `get_item_key`. Changed items are patched into the
+    /// prefix sums in `O(log n)` each; if many items change, a single `O(n)` rebuild is used
+    /// instead.
+    ///
+    /// This assumes the key mapping has not changed since the last rebuild (call `sync_item_keys`
+    /// first otherwise). Notifies only if some estimate changed.
+    pub fn refresh_estimates_incremental(&mut self) {
+        let count = self.options.count;
+        // Beyond this many changes, per-index updates cost more than a single rebuild.
+        let max_incremental = count / (usize::BITS - count.leading_zeros()).max(1) as usize;
+        let mut changed = 0usize;
+        for i in 0..count {
+            if self.measured[i] || self.is_always_pinned(i) {
+                continue;
+            }
+            let next = self.estimate_for(i);
+            let cur = self.sizes[i];
+            if next == cur {
+                continue;
+            }
+            self.sizes[i] = next;
+            changed += 1;
+            if changed <= max_incremental {
+                self.sums.add(i, next as i64 - cur as i64);
+            }
+        }
+        vdebug!(count, changed, "refresh_estimates_incremental");
+        if changed > 0 {
+            self.uniform_size.set(None);
+        }
+        if changed > max_incremental {
+            self.rebuild_fenwick();
+        }
+        if changed > 0 {
+            self.notify_structural(StructuralChange::Estimates);
+            self.notify(ChangeReason::Options);
+        }
+    }
+
+    pub fn reset_measurements(&mut self) {
+        self.key_sizes.clear();
+        self.measured_sum = 0;
+        self.measured_samples = 0;
+        self.rejected_measurements = 0;
+        self.cross_sizes = Vec::new();
+        self.max_cross_size = 0;
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Measure);
+    }
+
+    /// Records the cross-axis size of `index` (e.g. a row's width in a vertical list).
+    ///
+    /// Cross sizes are tracked by index only: they do not affect main-axis layout, are not part of
+    /// the key cache and do not trigger `on_change`. Storage is allocated on the first call.
+    /// Cleared by `reset_measurements` and truncated when `count` shrinks.
+    ///
+    /// Returns `true` if the stored cross size changed.
+    pub fn measure_cross(&mut self, index: usize, cross: u32) -> bool {
+        let count = self.options.count;
+        if index >= count {
+            return false;
+        }
+        if self.cross_sizes.len() < count {
+            self.cross_sizes.resize(count, 0);
+        }
+        let prev = core::mem::replace(&mut self.cross_sizes[index], cross);
+        if prev == cross {
+            return false;
+        }
+        if cross >= self.max_cross_size {
+            self.max_cross_size = cross;
+        } else if prev == self.max_cross_size {
+            self.recompute_max_cross_size();
+        }
+        true
+    }
+
+    /// Returns the largest cross-axis size recorded via [`Self::measure_cross`] (0 if none).
+    pub fn max_cross_size(&self) -> u32 {
+        self.max_cross_size
+    }
+
+    fn recompute_max_cross_size(&mut self) {
+        self.max_cross_size = self.cross_sizes.iter().copied().max().unwrap_or(0);
+    }
+
+    fn truncate_cross_sizes(&mut self, count: usize) {
+        if self.cross_sizes.len() > count {
+            self.cross_sizes.truncate(count);
+            self.recompute_max_cross_size();
+        }
+    }
+
+    /// Returns the number of cached measured sizes (key → size).
+    pub fn measurement_cache_len(&self) -> usize {
+        self.key_sizes.len()
+    }
+
+    /// Drops cached measurements whose key fails `keep`, then rebuilds per-index sizes.
+    ///
+    /// Use this to evict keys that left the dataset in long-lived sessions (the key cache
+    /// otherwise only grows). Items whose key was dropped fall back to their estimate.
+    pub fn retain_measurements(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        let before = self.key_sizes.len();
+        self.key_sizes.retain(|k, _| keep(k));
+        let removed = before - self.key_sizes.len();
+        vdebug!(removed, "retain_measurements");
+        if removed == 0 {
+            return;
+        }
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Measure);
+    }
+
+    /// Iterates over the cached measured sizes (key → size) without allocations.
+    pub fn for_each_cached_size(&self, mut f: impl FnMut(&K, u32)) {
+        for (k, v) in self.key_sizes.iter() {
+            f(k, *v);
+        }
+    }
+
+    /// Exports the cached measured sizes as a `Vec` (useful for persistence).
+    pub fn export_measurement_cache(&self) -> Vec<(K, u32)>
+    where
+        K: Clone,
+    {
+        let mut out = Vec::with_capacity(self.key_sizes.len());
+        self.for_each_cached_size(|k, v| out.push((k.clone(), v)));
+        out
+    }
+
+    /// Replaces the cached measured sizes from an iterator (useful when restoring state).
+    ///
+    /// Note: this rebuilds internal per-index sizes using the current key mapping.
+    pub fn import_measurement_cache(&mut self, entries: impl IntoIterator<Item = (K, u32)>) {
+        self.key_sizes.clear();
+        let mut n = 0usize;
+        for (k, v) in entries {
+            let v = self.clamp_size(v);
+            self.key_sizes.insert(k, v);
+            n = n.saturating_add(1);
+        }
+        vdebug!(entries = n, "import_measurement_cache");
+        self.rebuild_estimates();
+        self.notify(ChangeReason::Measure);
+    }
+
+    /// Marks an item as measured and updates its cached size.
+    ///
+    /// This is aligned with TanStack Virtual's "measure element" behavior: if the measured item is
+    /// before the current scroll offset, the virtualizer may adjust `scroll_offset` to prevent a
+    /// visible "jump".
+    ///
+    /// If you want to update measurements without any scroll adjustment, use
+    /// `measure_unadjusted`.
+    ///
+    /// Returns `true` if the item size changed (i.e. the layout may need a redraw), or `false` if
+    /// the measurement matched the current size (or `index` is out of bounds).
+    pub fn measure(&mut self, index: usize, size: u32) -> bool {
+        if index >= self.options.count {
+            return false;
+        }
+        let key = self.key_for(index);
+        self.measure_keyed(index, key, size)
+    }
+
+    /// Same as [`Self::measure`], but uses a precomputed key to avoid recomputing `get_item_key`.
+    pub fn measure_keyed(&mut self, index: usize, key: K, size: u32) -> bool {
+        if index >= self.options.count {
+            return false;
+        }
+        let (size_delta, _) = self.resize_item_keyed_inner(index, key, size);
+        size_delta != 0
+    }
+
+    /// Same as [`Self::measure`], but `size_with_gap` includes the gap rendered after the item
+    /// (see [`Self::item_size_with_gap`]).
+    ///
+    /// The gap is subtracted before storing; sizes smaller than the gap are clamped to 0.
+    pub fn measure_including_gap(&mut self, index: usize, size_with_gap: u32) -> bool {
+        if index >= self.options.count {
+            return false;
+        }
+        let size = size_with_gap.saturating_sub(self.gap_following(index));
+        self.measure(index, size)
+    }
+
+    /// Marks an item as measured and updates its cached size without adjusting `scroll_offset`.
+    pub fn measure_unadjusted(&mut self, index: usize, size: u32) {
+        if index >= self.options.count {
+            return;
+        }
+        let key = self.key_for(index);
+        self.measure_keyed_unadjusted(index, key, size);
+    }
+
+    /// Same as [`Self::measure_unadjusted`], but uses a precomputed key.
+    pub fn measure_keyed_unadjusted(&mut self, index: usize, key: K, size: u32) {
+        if index >= self.options.count {
+            return;
         }
         vtrace!(index, size, "measure_keyed_unadjusted");
-        self.set_item_size_keyed(index, key, size);
-        self.notify();
+        let delta = self.set_item_size_keyed(index, key, size);
+        if delta == 0 {
+            return;
+        }
+        if delta < 0 {
+            self.clamp_scroll_after_measure();
+        }
+        self.notify(ChangeReason::Measure);
     }
 
     pub fn resize_item(&mut self, index: usize, size: u32) -> i64 {
@@ -653,14 +1391,48 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         if index >= self.options.count {
             return 0;
         }
+        let (_, applied) = self.resize_item_keyed_inner(index, key, size);
+        applied
+    }
+
+    /// Applies a signed size delta to the current size of `index` (saturating at 0 and
+    /// `u32::MAX`), for layout engines that report incremental changes.
+    ///
+    /// Behaves like [`Self::resize_item`] with the resulting absolute size: the key cache is
+    /// updated and the applied scroll adjustment is returned.
+    pub fn adjust_item_size(&mut self, index: usize, delta: i64) -> i64 {
+        if index >= self.options.count {
+            return 0;
+        }
+        // Pinned items are 0 in the layout; adjust their overlay size instead.
+        let cur = if self.is_always_pinned(index) {
+            self.pinned_size(index)
+        } else {
+            self.sizes[index]
+        };
+        let size = (cur as i64).saturating_add(delta).clamp(0, u32::MAX as i64) as u32;
+        self.resize_item(index, size)
+    }
+
+    /// Returns `(size_delta, applied_scroll_delta)`.
+    fn resize_item_keyed_inner(&mut self, index: usize, key: K, size: u32) -> (i64, i64) {
+        if self.options.freeze_measured && self.measured[index] {
+            vtrace!(index, "resize_item ignored (freeze_measured)");
+            return (0, 0);
+        }
+        if self.options.provisional {
+            vtrace!(index, "resize_item ignored (provisional)");
+            return (0, 0);
+        }
         let item = self.item(index);
         let delta = self.set_item_size_keyed(index, key, size);
         if delta == 0 {
-            self.notify();
-            return 0;
+            return (0, 0);
         }
 
-        let should_adjust = if let Some(f) = &self
+        let should_adjust = if !self.options.adjust_scroll_on_resize {
+            false
+        } else if let Some(f) = &self
             .options
             .should_adjust_scroll_position_on_item_size_change
         {
@@ -669,22 +1441,56 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             item.start < self.scroll_offset
         };
 
-        if should_adjust {
+        let applied = if should_adjust {
             if delta > 0 {
                 self.scroll_offset = self.scroll_offset.saturating_add(delta as u64);
             } else {
                 self.scroll_offset = self.scroll_offset.saturating_sub((-delta) as u64);
             }
-            self.notify();
             delta
         } else {
-            self.notify();
             0
+        };
+        if delta < 0 {
+            self.clamp_scroll_after_measure();
         }
+        self.notify(ChangeReason::Measure);
+        (delta, applied)
+    }
+
+    /// Resizes the item at `offset` (resolved via [`Self::index_at_offset`]).
+    ///
+    /// This is useful when your UI reports size changes by position rather than by index. Returns
+    /// the applied scroll adjustment (see [`Self::resize_item`]), or 0 if `offset` maps to no item
+    /// (disabled/empty).
+    pub fn resize_item_at_offset(&mut self, offset: u64, new_size: u32) -> i64 {
+        let Some(index) = self.index_at_offset(offset) else {
+            return 0;
+        };
+        self.resize_item(index, new_size)
     }
 
     fn set_item_size_keyed(&mut self, index: usize, key: K, size: u32) -> i64 {
+        if self.ignores_measurement(index) {
+            return 0;
+        }
+        let size = self.clamp_size(size);
+        if self.reject_outlier(size) {
+            return 0;
+        }
+        if self.is_always_pinned(index) {
+            // Overlay-only: cache the size, but keep the item out of the layout. The layout delta
+            // is 0, so notify here if the overlay itself changed.
+            self.measured[index] = true;
+            if self.key_sizes.insert(key, size) != Some(size) {
+                self.notify(ChangeReason::Measure);
+            }
+            return 0;
+        }
         let cur = self.sizes[index];
+        if cur != size || !self.measured[index] {
+            self.record_measurement_sample(size);
+        }
         if cur == size {
             self.measured[index] = true;
             self.key_sizes.insert(key, size);
@@ -693,6 +1499,7 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         self.sizes[index] = size;
         self.measured[index] = true;
         self.key_sizes.insert(key, size);
+        self.uniform_size.set(None);
         let delta = size as i64 - cur as i64;
         self.sums.add(index, delta);
         delta
@@ -701,32 +1508,80 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     /// Measures multiple items in one pass.
     ///
     /// Like [`Self::measure`], this may adjust `scroll_offset` to prevent jumps.
+    ///
+    /// Does not notify if no size changed.
     pub fn measure_many(&mut self, measurements: impl IntoIterator<Item = (usize, u32)>) {
         let _ = self.resize_item_many(measurements);
     }
 
+    /// Same as [`Self::measure_many`], but returns `true` if any item size changed.
+    ///
+    /// On a no-op frame (all sizes unchanged) no `on_change` notification fires, so adapters can
+    /// skip redraws entirely.
+    pub fn apply_measurements(&mut self, measurements: &[(usize, u32)]) -> bool {
+        let mut changed = false;
+        self.batch_update(|v| {
+            for &(index, size) in measurements {
+                if index >= v.options.count || v.record_if_unchanged(index, size) {
+                    continue;
+                }
+                let key = v.key_for(index);
+                let (size_delta, _) = v.resize_item_keyed_inner(index, key, size);
+                changed |= size_delta != 0;
+            }
+        });
+        changed
+    }
+
+    /// Same as [`Self::measure_many`], but writes the indexes whose size actually changed into
+    /// `changed_out` (cleared first, in input order).
+    ///
+    /// Useful for re-laying out (or animating) only the items that changed this frame. A no-op
+    /// batch leaves `changed_out` empty and does not notify.
+    pub fn measure_many_into(
+        &mut self,
+        measurements: impl IntoIterator<Item = (usize, u32)>,
+        changed_out: &mut Vec<usize>,
+    ) {
+        changed_out.clear();
+        self.batch_update(|v| {
+            for (index, size) in measurements {
+                if index >= v.options.count || v.record_if_unchanged(index, size) {
+                    continue;
+                }
+                let key = v.key_for(index);
+                let (size_delta, _) = v.resize_item_keyed_inner(index, key, size);
+                if size_delta != 0 {
+                    changed_out.push(index);
+                }
+            }
+        });
+    }
+
     /// Measures multiple items without adjusting `scroll_offset`.
+    ///
+    /// Does not notify if no size changed.
     pub fn measure_many_unadjusted(
         &mut self,
         measurements: impl IntoIterator<Item = (usize, u32)>,
     ) {
+        let mut changed = false;
+        let mut shrunk = false;
         for (index, size) in measurements {
             if index >= self.options.count {
                 continue;
             }
             let key = self.key_for(index);
-            let cur = self.sizes[index];
-            if cur == size {
-                self.measured[index] = true;
-                self.key_sizes.insert(key, size);
-                continue;
-            }
-            self.sizes[index] = size;
-            self.measured[index] = true;
-            self.key_sizes.insert(key, size);
-            self.sums.add(index, size as i64 - cur as i64);
+            let delta = self.set_item_size_keyed(index, key, size);
+            changed |= delta != 0;
+            shrunk |= delta < 0;
+        }
+        if shrunk {
+            self.clamp_scroll_after_measure();
+        }
+        if changed {
+            self.notify(ChangeReason::Measure);
         }
-        self.notify();
     }
 
     pub fn resize_item_many(
@@ -736,7 +1591,7 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         let mut applied = 0i64;
         self.batch_update(|v| {
             for (index, size) in measurements {
-                if index >= v.options.count {
+                if index >= v.options.count || v.record_if_unchanged(index, size) {
                     continue;
                 }
                 applied += v.resize_item(index, size);
@@ -745,19 +1600,276 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         applied
     }
 
+    /// Applies `clamp_scroll_after_measure` (no-op unless enabled).
+    fn clamp_scroll_after_measure(&mut self) {
+        if self.options.clamp_scroll_after_measure {
+            self.scroll_offset = self.clamp_scroll_offset(self.scroll_offset);
+        }
+    }
+
+    /// `provisional` ignores all measurements; `freeze_measured` ignores re-measurements.
+    fn ignores_measurement(&self, index: usize) -> bool {
+        self.options.provisional || (self.options.freeze_measured && self.measured[index])
+    }
+
+    /// Records a measurement that matches the current size (marks the item measured and caches
+    /// it by key) without notifying. Returns `false` if the size differs.
+    fn record_if_unchanged(&mut self, index: usize, size: u32) -> bool {
+        let size = self.clamp_size(size);
+        if self.sizes[index] != size {
+            return false;
+        }
+        if !self.ignores_measurement(index) && !self.reject_outlier(size) {
+            if !self.measured[index] && !self.is_always_pinned(index) {
+                self.record_measurement_sample(size);
+            }
+            let key = self.key_for(index);
+            self.measured[index] = true;
+            self.key_sizes.insert(key, size);
+        }
+        true
+    }
+
     pub fn is_measured(&self, index: usize) -> bool {
         self.measured.get(index).copied().unwrap_or(false)
     }
 
+    /// Returns the length of the leading run of measured items (`0` if item 0 is unmeasured).
+    ///
+    /// Offsets up to the end of this prefix are exact; use it to decide whether a far
+    /// `scroll_to_index` target is trustworthy or should be pre-measured first. Runs in `O(n)`.
+    pub fn contiguous_measured_prefix(&self) -> usize {
+        self.measured.iter().take_while(|&&m| m).count()
+    }
+
+    /// Returns the fraction of items in `range` (clamped to `[0, count)`) that are measured.
+    ///
+    /// Returns `1.0` for an empty range.
+    pub fn measured_ratio_in(&self, range: VirtualRange) -> f32 {
+        let end = range.end_index.min(self.measured.len());
+        let start = range.start_index.min(end);
+        if start == end {
+            return 1.0;
+        }
+        let measured = self.measured[start..end].iter().filter(|&&m| m).count();
+        measured as f32 / (end - start) as f32
+    }
+
     pub fn total_size(&self) -> u64 {
         if !self.options.enabled {
             return 0;
         }
-        self.options.padding_start as u64 + self.sums.total() + self.options.padding_end as u64
+        self.start_inset()
+            .saturating_add(self.sums.total())
+            .saturating_add(self.end_inset())
+    }
+
+    /// Returns the size spanned by `range`, from the first item's start to the last item's end.
+    ///
+    /// Gaps between items in the range are included; gaps outside the range and padding are not.
+    /// Returns 0 for empty ranges or when disabled.
+    pub fn content_size_in(&self, range: VirtualRange) -> u64 {
+        let end_index = range.end_index.min(self.options.count);
+        if !self.options.enabled || range.start_index >= end_index {
+            return 0;
+        }
+        let last = end_index - 1;
+        let end = self.start_of(last).saturating_add(self.sizes[last] as u64);
+        end.saturating_sub(self.start_of(range.start_index))
+    }
+
+    /// Returns the size of items `[0, index)` in list coordinates (`index` is clamped to `count`).
+    ///
+    /// This includes `padding_start`, `leading_gap` and the gaps after each of those items, but not
+    /// `scroll_margin` (unlike [`Self::item_start`]). Returns 0 when disabled.
+    pub fn content_size_before(&self, index: usize) -> u64 {
+        if !self.options.enabled {
+            return 0;
+        }
+        self.start_of(index.min(self.options.count))
+    }
+
+    /// Returns the size of items `[index, count)` in list coordinates, including `trailing_gap`
+    /// and `padding_end` (`index` is clamped to `count`).
+    ///
+    /// `content_size_before(i) + content_size_after(i) == total_size()` for any `i`. Returns 0
+    /// when disabled.
+    pub fn content_size_after(&self, index: usize) -> u64 {
+        self.total_size()
+            .saturating_sub(self.content_size_before(index))
+    }
+
+    /// Returns the space before the first rendered (overscanned) item, in list coordinates.
+    ///
+    /// This includes `padding_start`, `leading_gap` and gaps, but not `scroll_margin`; it is the
+    /// size a DOM-style adapter needs for a leading spacer. Returns 0 when nothing is rendered.
+    pub fn leading_space(&self) -> u64 {
+        let range = self.virtual_range();
+        if range.is_empty() {
+            return 0;
+        }
+        self.start_of(range.start_index)
+    }
+
+    /// Returns the space after the last rendered (overscanned) item, in list coordinates.
+    ///
+    /// This includes the gap after the last rendered item, `trailing_gap` and `padding_end`, so
+    /// that `leading_space + content_size_in(virtual_range) + trailing_space == total_size`.
+    /// Returns `total_size` when nothing is rendered.
+    pub fn trailing_space(&self) -> u64 {
+        let range = self.virtual_range();
+        if range.is_empty() {
+            return self.total_size();
+        }
+        let rendered_end = self
+            .start_of(range.start_index)
+            .saturating_add(self.content_size_in(range));
+        self.total_size().saturating_sub(rendered_end)
+    }
+
+    /// Resolves `keys` to their current indexes via `key_to_index`, returned sorted and
+    /// deduplicated.
+    ///
+    /// Keys that don't resolve, or resolve outside `[0, count)`, are skipped. Pass
+    /// `|k| v.index_for_key(k)` when [`VirtualizerOptions::maintain_key_index`] is enabled, or an
+    /// adapter-side lookup otherwise (as with `apply_anchor`).
+    pub fn indices_for_keys(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        key_to_index: impl Fn(&K) -> Option<usize>,
+    ) -> Vec<usize> {
+        let count = self.options.count;
+        let mut out: Vec<usize> = keys
+            .into_iter()
+            .filter_map(|k| key_to_index(&k))
+            .filter(|&i| i < count)
+            .collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    }
+
+    /// Returns the (window-local) index for `key`, using the internal key → index map.
+    ///
+    /// Always returns `None` unless [`VirtualizerOptions::maintain_key_index`] is enabled. The map
+    /// is rebuilt on structural changes (`count`, `get_item_key`, window, `sync_item_keys`); call
+    /// `sync_item_keys` after reordering data in place.
+    pub fn index_for_key(&self, key: &K) -> Option<usize> {
+        self.key_index.get(key).copied()
     }
 
+    /// Returns the key for `index` (calls `get_item_key` with the absolute index).
     pub fn key_for(&self, index: usize) -> K {
-        (self.options.get_item_key)(index)
+        (self.options.get_item_key)(self.options.window_base.saturating_add(index))
+    }
+
+    fn estimate_for(&self, index: usize) -> u32 {
+        let abs = self.options.window_base.saturating_add(index);
+        let size = match &self.options.estimate_size_ctx {
+            Some(f) => f(
+                abs,
+                &EstimateCtx {
+                    base: self.options.window_base,
+                    sizes: &self.sizes,
+                    measured: &self.measured,
+                },
+            ),
+            None => match self.options.uniform_estimate {
+                Some(size) => size,
+                None => (self.options.estimate_size)(abs),
+            },
+        };
+        self.clamp_size(size)
+    }
+
+    fn clamp_size(&self, size: u32) -> u32 {
+        clamp_item_size(size, self.options.min_item_size, self.options.max_item_size)
+    }
+
+    /// Returns the number of items the internal per-index buffers can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.sizes
+            .capacity()
+            .min(self.measured.capacity())
+            .min(self.sums.capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more items in the internal buffers.
+    ///
+    /// Useful before a large `count` increase to avoid repeated reallocation.
+    pub fn reserve(&mut self, additional: usize) {
+        self.sizes.reserve(additional);
+        self.measured.reserve(additional);
+        self.sums.reserve(additional);
+    }
+
+    /// Releases spare capacity of the internal buffers and the measurement cache.
+    ///
+    /// Buffers keep their capacity when `count` shrinks; call this after large shrinks in
+    /// long-running apps.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    /// Like [`Self::shrink_to_fit`], but keeps capacity for at least `min_capacity` items.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.sizes.shrink_to(min_capacity);
+        self.measured.shrink_to(min_capacity);
+        self.sums.shrink_to(min_capacity);
+        shrink_key_sizes(&mut self.key_sizes, min_capacity);
+    }
+
+    /// Returns the first absolute index of the current window (see
+    /// [`VirtualizerOptions::window_base`]).
+    pub fn window_base(&self) -> usize {
+        self.options.window_base
+    }
+
+    /// Converts a (window-local) index into an absolute dataset index.
+    pub fn absolute_index(&self, index: usize) -> usize {
+        self.options.window_base.saturating_add(index)
+    }
+
+    /// Moves the window to `[new_base, new_base + new_len)` in absolute indexes.
+    ///
+    /// - Measurements for items that remain inside the window are reused (by key); cached sizes
+    ///   for items outside the new window are dropped, keeping memory bounded by the window size.
+    /// - If the first visible item is still inside the window, `scroll_offset` is adjusted so it
+    ///   stays at the same position in the viewport.
+    pub fn rebase_window(&mut self, new_base: usize, new_len: usize) {
+        let anchor = {
+            let visible = self.visible_range();
+            (!visible.is_empty()).then(|| {
+                let index = visible.start_index;
+                let start = self.item(index).start;
+                (
+                    self.absolute_index(index),
+                    self.scroll_offset.saturating_sub(start),
+                )
+            })
+        };
+        vdebug!(new_base, new_len, "rebase_window");
+
+        self.options.window_base = new_base;
+        self.options.count = new_len;
+
+        let mut prev = core::mem::take(&mut self.key_sizes);
+        for i in 0..new_len {
+            let key = self.key_for(i);
+            if let Some(size) = prev.remove(&key) {
+                self.key_sizes.insert(key, size);
+            }
+        }
+        self.rebuild_estimates();
+
+        if let Some((abs, offset_in_item)) = anchor
+            && abs >= new_base
+            && abs - new_base < new_len
+        {
+            let start = self.item(abs - new_base).start;
+            self.scroll_offset = self.clamp_scroll_offset(start.saturating_add(offset_in_item));
+        }
+        self.notify(ChangeReason::Count);
     }
 
     pub fn virtual_range(&self) -> VirtualRange {
@@ -780,6 +1892,102 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         self.compute_range(scroll_offset, viewport_size)
     }
 
+    /// Returns the fully-rendered and placeholder overscan bands around the visible range.
+    ///
+    /// The first range equals [`Self::virtual_range`] (visible + overscan); the second extends
+    /// it by a further `placeholder_overscan` items on each side (clamped to `[0, count)`).
+    /// Adapters can render the difference as cheap skeleton rows so fast scrolls don't show
+    /// blank space. Both ranges are empty when nothing is visible.
+    pub fn overscan_bands(&self) -> (VirtualRange, VirtualRange) {
+        let visible = self.visible_range();
+        if visible.is_empty() {
+            return (visible, visible);
+        }
+        let full = self.overscanned(visible);
+        let placeholder = self.expand_range(full, self.options.placeholder_overscan);
+        (full, placeholder)
+    }
+
+    /// Returns up to `ahead` indexes just past the overscanned range in the current
+    /// `scroll_direction` (clamped to `[0, count)`).
+    ///
+    /// This is intended for prefetching data (not rendering). Returns an empty range when the
+    /// scroll direction is unknown or the virtualizer is disabled.
+    pub fn prefetch_range(&self, ahead: usize) -> VirtualRange {
+        let empty = VirtualRange {
+            start_index: 0,
+            end_index: 0,
+        };
+        let range = self.virtual_range();
+        if range.is_empty() {
+            return empty;
+        }
+        match self.scroll_direction {
+            Some(ScrollDirection::Forward) => VirtualRange {
+                start_index: range.end_index,
+                end_index: range
+                    .end_index
+                    .saturating_add(ahead)
+                    .min(self.options.count),
+            },
+            Some(ScrollDirection::Backward) => VirtualRange {
+                start_index: range.start_index.saturating_sub(ahead),
+                end_index: range.start_index,
+            },
+            None => empty,
+        }
+    }
+
+    /// Same as [`Self::prefetch_range`], but the band covers `ahead_px` pixels of content past
+    /// the overscanned range (items partially inside the band are included).
+    ///
+    /// Useful for fetchers that think in screenfuls rather than item counts.
+    pub fn prefetch_range_px(&self, ahead_px: u32) -> VirtualRange {
+        let empty = VirtualRange {
+            start_index: 0,
+            end_index: 0,
+        };
+        let range = self.virtual_range();
+        if range.is_empty() || ahead_px == 0 {
+            return empty;
+        }
+        let count = self.options.count;
+        let px = ahead_px as u64;
+        match self.scroll_direction {
+            Some(ScrollDirection::Forward) => {
+                let start_index = range.end_index;
+                let end_index = if start_index >= count {
+                    count
+                } else {
+                    let band_end = self.start_of(start_index).saturating_add(px);
+                    self.index_at_offset_inner_list(band_end - 1)
+                        .map_or(start_index, |i| i + 1)
+                        .clamp(start_index, count)
+                };
+                VirtualRange {
+                    start_index,
+                    end_index,
+                }
+            }
+            Some(ScrollDirection::Backward) => {
+                let end_index = range.start_index;
+                let start_index = if end_index == 0 {
+                    0
+                } else {
+                    let band_start = self.start_of(end_index).saturating_sub(px);
+                    self.index_at_offset_inner_list(band_start)
+                        .unwrap_or(0)
+                        .min(end_index)
+                };
+                VirtualRange {
+                    start_index,
+                    end_index,
+                }
+            }
+            None => empty,
+        }
+    }
+
     pub fn visible_range(&self) -> VirtualRange {
         if !self.options.enabled {
             return VirtualRange {
@@ -797,7 +2005,106 @@ impl<K: KeyCacheKey> Virtualizer<K> {
                 end_index: 0,
             };
         }
-        self.compute_visible_range(scroll_offset, viewport_size)
+        self.compute_visible_range(scroll_offset, viewport_size)
+    }
+
+    /// Returns the number of items in the strict visible range (no overscan).
+    pub fn visible_item_count(&self) -> usize {
+        self.visible_range().len()
+    }
+
+    /// Returns the number of indexes [`Self::for_each_virtual_index`] yields: the overscanned
+    /// range, or the `range_extractor` output (minus `always_pinned` items) when set.
+    ///
+    /// Without an extractor or pinned items this is `O(log n)`; otherwise the emitted indexes
+    /// are counted.
+    pub fn virtual_item_count(&self) -> usize {
+        if self.options.range_extractor.is_none() && self.options.always_pinned.is_empty() {
+            return self.virtual_range().len();
+        }
+        let mut n = 0usize;
+        self.for_each_virtual_index(|_| n += 1);
+        n
+    }
+
+    /// Returns `true` if `index` is within the visible range (no overscan).
+    ///
+    /// Always returns `false` when disabled.
+    pub fn is_index_visible(&self, index: usize) -> bool {
+        self.visible_range().contains(index)
+    }
+
+    /// Returns the visible item whose center is nearest the viewport center (e.g. for
+    /// snap-to-center carousels).
+    ///
+    /// Distances are compared exactly (no half-pixel rounding); ties go to the lower index.
+    /// Returns `None` when disabled or nothing is visible.
+    pub fn most_centered_index(&self) -> Option<usize> {
+        let visible = self.visible_range();
+        if visible.is_empty() {
+            return None;
+        }
+        // Compare doubled centers to stay in integers.
+        let view_center2 = (self.scroll_offset.min(self.max_scroll_offset()) as u128) * 2
+            + self.viewport_size as u128;
+        visible.iter().min_by_key(|&i| {
+            let item = self.item(i);
+            let center2 = item.start as u128 * 2 + item.size as u128;
+            center2.abs_diff(view_center2)
+        })
+    }
+
+    /// Returns `true` if `index` is within the overscanned range but not visible.
+    ///
+    /// Always returns `false` when disabled.
+    pub fn is_index_in_overscan(&self, index: usize) -> bool {
+        self.virtual_range().contains(index) && !self.is_index_visible(index)
+    }
+
+    /// Iterates the overscan-only indexes (rendered but not visible) ordered by increasing pixel
+    /// distance from the nearest viewport edge, as `(index, distance)`.
+    ///
+    /// This is a recycling hint: an adapter with a fixed budget of realized items can drop
+    /// overscan items from the end of this sequence first. Ties are broken in favour of the item
+    /// before the viewport. Indexes pinned by a `range_extractor` are not included.
+    pub fn overscan_items_by_distance(&self, mut f: impl FnMut(usize, u64)) {
+        let visible = self.visible_range();
+        if visible.is_empty() {
+            return;
+        }
+        let range = self.virtual_range();
+        let view_start = self.scroll_offset;
+        let view_end = view_start.saturating_add(self.viewport_size as u64);
+
+        let mut before = visible.start_index;
+        let mut after = visible.end_index;
+        let before_distance = |i: usize| view_start.saturating_sub(self.item(i).end());
+        let after_distance = |i: usize| self.item(i).start.saturating_sub(view_end);
+        loop {
+            let next_before = (before > range.start_index).then(|| before - 1);
+            let next_after = (after < range.end_index).then_some(after);
+            match (next_before, next_after) {
+                (Some(b), Some(a)) => {
+                    let (db, da) = (before_distance(b), after_distance(a));
+                    if db <= da {
+                        f(b, db);
+                        before = b;
+                    } else {
+                        f(a, da);
+                        after = a + 1;
+                    }
+                }
+                (Some(b), None) => {
+                    f(b, before_distance(b));
+                    before = b;
+                }
+                (None, Some(a)) => {
+                    f(a, after_distance(a));
+                    after = a + 1;
+                }
+                (None, None) => break,
+            }
+        }
     }
 
     pub fn for_each_virtual_index(&self, f: impl FnMut(usize)) {
@@ -828,13 +2135,19 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         };
 
         if let Some(extract) = &self.options.range_extractor {
+            let validate = self.options.validate_extractor;
             let mut prev: Option<usize> = None;
             extract(range, &mut |i| {
                 if i >= count {
-                    debug_assert!(
-                        i < count,
-                        "range_extractor emitted out-of-bounds index (i={i}, count={count})"
-                    );
+                    if validate {
+                        vwarn!(i, count, "range_extractor emitted out-of-bounds index");
+                        self.record_extractor_violation();
+                    } else {
+                        debug_assert!(
+                            i < count,
+                            "range_extractor emitted out-of-bounds index (i={i}, count={count})"
+                        );
+                    }
                     return;
                 }
                 if let Some(p) = prev {
@@ -842,28 +2155,71 @@ impl<K: KeyCacheKey> Virtualizer<K> {
                         return;
                     }
                     if i < p {
-                        debug_assert!(
-                            i > p,
-                            "range_extractor must emit sorted indexes (prev={p}, next={i})"
-                        );
+                        if validate {
+                            vwarn!(prev = p, next = i, "range_extractor emitted unsorted index");
+                            self.record_extractor_violation();
+                        } else {
+                            debug_assert!(
+                                i > p,
+                                "range_extractor must emit sorted indexes (prev={p}, next={i})"
+                            );
+                        }
                         return;
                     }
-                    debug_assert!(
-                        i > p,
-                        "range_extractor must emit sorted indexes (prev={p}, next={i})"
-                    );
                 }
                 prev = Some(i);
-                f(i);
+                if !self.is_always_pinned(i) {
+                    f(i);
+                }
             });
             return;
         }
 
-        let overscan = self.options.overscan;
-        let start = visible.start_index.saturating_sub(overscan);
-        let end = cmp::min(count, visible.end_index.saturating_add(overscan));
-        for i in start..end {
-            f(i);
+        for i in self.overscanned(visible) {
+            if !self.is_always_pinned(i) {
+                f(i);
+            }
+        }
+    }
+
+    /// Iterates the rendered indexes coalesced into maximal contiguous `[start, end)` runs.
+    ///
+    /// With a `range_extractor` that pins scattered indexes, each pinned island becomes its own
+    /// run, so adapters can place spacers between runs. Overlay-pinned items (`always_pinned`)
+    /// are skipped, splitting the runs around them. Otherwise this yields a single run (the
+    /// overscanned range).
+    pub fn for_each_virtual_run(&self, f: impl FnMut(VirtualRange)) {
+        self.for_each_virtual_run_for(self.scroll_offset, self.viewport_size, f);
+    }
+
+    pub fn for_each_virtual_run_for(
+        &self,
+        scroll_offset: u64,
+        viewport_size: u32,
+        mut f: impl FnMut(VirtualRange),
+    ) {
+        if self.options.range_extractor.is_none() && self.options.always_pinned.is_empty() {
+            let range = self.virtual_range_for(scroll_offset, viewport_size);
+            if !range.is_empty() {
+                f(range);
+            }
+            return;
+        }
+
+        let mut run: Option<VirtualRange> = None;
+        self.for_each_virtual_index_for(scroll_offset, viewport_size, |i| match &mut run {
+            Some(r) if r.end_index == i => r.end_index = i + 1,
+            _ => {
+                if let Some(r) = run.replace(VirtualRange {
+                    start_index: i,
+                    end_index: i + 1,
+                }) {
+                    f(r);
+                }
+            }
+        });
+        if let Some(r) = run {
+            f(r);
         }
     }
 
@@ -893,29 +2249,82 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             return;
         }
 
-        let count = self.options.count;
-        let overscan = self.options.overscan;
-        let start_index = visible.start_index.saturating_sub(overscan);
-        let end_index = cmp::min(count, visible.end_index.saturating_add(overscan));
+        let VirtualRange {
+            start_index,
+            end_index,
+        } = self.overscanned(visible);
         if start_index >= end_index {
             return;
         }
 
-        let margin = self.options.scroll_margin as u64;
-        let mut start = margin.saturating_add(self.start_of(start_index));
-        let gap = self.options.gap as u64;
-
+        let mut start = self.start_of(start_index);
         for i in start_index..end_index {
             let size = self.sizes[i];
-            f(VirtualItem {
-                index: i,
-                start,
-                size,
-            });
+            if !self.is_always_pinned(i) {
+                f(VirtualItem {
+                    index: i,
+                    start: self.list_to_container(start),
+                    size,
+                });
+            }
 
-            start = start.saturating_add(size as u64);
-            if gap > 0 && i + 1 < count {
-                start = start.saturating_add(gap);
+            start = start
+                .saturating_add(size as u64)
+                .saturating_add(self.gap_after(i) as u64);
+        }
+    }
+
+    /// Same as [`Self::for_each_virtual_item`], but yields items in descending index order.
+    ///
+    /// This is useful for renderers that lay out bottom-up (e.g. chat UIs anchored to the
+    /// viewport end). Starts are identical to the forward pass.
+    ///
+    /// With a `range_extractor`, the emitted indexes are buffered (one allocation) to reverse
+    /// them.
+    pub fn for_each_virtual_item_rev(&self, f: impl FnMut(VirtualItem)) {
+        self.for_each_virtual_item_rev_for(self.scroll_offset, self.viewport_size, f);
+    }
+
+    pub fn for_each_virtual_item_rev_for(
+        &self,
+        scroll_offset: u64,
+        viewport_size: u32,
+        mut f: impl FnMut(VirtualItem),
+    ) {
+        if !self.options.enabled {
+            return;
+        }
+
+        if self.options.range_extractor.is_some() {
+            let mut indexes = Vec::new();
+            self.for_each_virtual_index_for(scroll_offset, viewport_size, |i| indexes.push(i));
+            for &i in indexes.iter().rev() {
+                f(self.item(i));
+            }
+            return;
+        }
+
+        let range = self.virtual_range_for(scroll_offset, viewport_size);
+        if range.is_empty() {
+            return;
+        }
+
+        let last = range.end_index - 1;
+        let mut start = self.start_of(last);
+        for i in range.iter().rev() {
+            let size = self.sizes[i];
+            if !self.is_always_pinned(i) {
+                f(VirtualItem {
+                    index: i,
+                    start: self.list_to_container(start),
+                    size,
+                });
+            }
+
+            if i > range.start_index {
+                start = start
+                    .saturating_sub(self.gap_after(i - 1) as u64)
+                    .saturating_sub(self.sizes[i - 1] as u64);
             }
         }
     }
@@ -952,31 +2361,29 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             return;
         }
 
-        let count = self.options.count;
-        let overscan = self.options.overscan;
-        let start_index = visible.start_index.saturating_sub(overscan);
-        let end_index = cmp::min(count, visible.end_index.saturating_add(overscan));
+        let VirtualRange {
+            start_index,
+            end_index,
+        } = self.overscanned(visible);
         if start_index >= end_index {
             return;
         }
 
-        let margin = self.options.scroll_margin as u64;
-        let mut start = margin.saturating_add(self.start_of(start_index));
-        let gap = self.options.gap as u64;
-
+        let mut start = self.start_of(start_index);
         for i in start_index..end_index {
             let size = self.sizes[i];
-            f(VirtualItemKeyed {
-                key: self.key_for(i),
-                index: i,
-                start,
-                size,
-            });
-
-            start = start.saturating_add(size as u64);
-            if gap > 0 && i + 1 < count {
-                start = start.saturating_add(gap);
+            if !self.is_always_pinned(i) {
+                f(VirtualItemKeyed {
+                    key: self.key_for(i),
+                    index: i,
+                    start: self.list_to_container(start),
+                    size,
+                });
             }
+
+            start = start
+                .saturating_add(size as u64)
+                .saturating_add(self.gap_after(i) as u64);
         }
     }
 
@@ -995,7 +2402,90 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         offset
     }
 
+    /// Scrolls by `delta` items (negative = backwards) relative to the item at the current
+    /// scroll offset, aligning the target with `align` (e.g. for arrow-key navigation).
+    ///
+    /// Moves by real item sizes rather than a pixel guess. The current item is the one at
+    /// `scroll_offset + scroll_padding_start` (where `Align::Start` places its target). The target
+    /// index is clamped to `[0, count)`. Returns the applied (clamped) offset; when nothing is at
+    /// the current offset (empty or disabled), the offset is left unchanged.
+    pub fn scroll_by_items(&mut self, delta: i64, align: Align) -> u64 {
+        let reference = self
+            .scroll_offset
+            .saturating_add(self.options.scroll_padding_start as u64);
+        let Some(top) = self.index_at_offset(reference) else {
+            return self.scroll_offset;
+        };
+        let last = self.options.count - 1;
+        let target = if delta < 0 {
+            top.saturating_sub(delta.unsigned_abs().try_into().unwrap_or(usize::MAX))
+        } else {
+            top.saturating_add(delta.try_into().unwrap_or(usize::MAX))
+                .min(last)
+        };
+        self.scroll_to_index(target, align)
+    }
+
+    /// Like [`Self::scroll_to_index`], but returns the resulting [`Self::virtual_range`].
+    ///
+    /// The range is computed from the just-applied offset, so it can drive data fetching directly.
+    pub fn scroll_to_index_revealing(&mut self, index: usize, align: Align) -> VirtualRange {
+        self.scroll_to_index(index, align);
+        self.virtual_range()
+    }
+
     pub fn scroll_to_index_offset(&self, index: usize, align: Align) -> u64 {
+        self.scroll_to_index_offset_for(index, align, self.viewport_size)
+    }
+
+    /// Computes the (clamped) scroll offset that centers a list-space `content_offset` in the
+    /// viewport, without snapping to an item.
+    ///
+    /// `content_offset` is relative to the list start (it is shifted by `scroll_margin`). Odd
+    /// viewport sizes are rounded per `center_rounding`. Useful for continuous/pannable content.
+    pub fn center_offset(&self, content_offset: u64) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        let point = self.list_to_container(content_offset);
+        let target = center_target(
+            point,
+            point,
+            self.viewport_size as u64,
+            self.options.center_rounding,
+        );
+        self.clamp_scroll_offset(target)
+    }
+
+    /// Programmatically centers `content_offset` (see [`Self::center_offset`]).
+    ///
+    /// Like `scroll_to_index`, this does not mark the virtualizer as "scrolling". Returns the
+    /// applied (clamped) offset.
+    pub fn scroll_to_center_offset(&mut self, content_offset: u64) -> u64 {
+        let offset = self.center_offset(content_offset);
+        self.set_scroll_offset(offset);
+        offset
+    }
+
+    /// Same as [`Self::scroll_to_index_offset`], but resolves `key` via [`Self::index_for_key`].
+    ///
+    /// Returns `None` if the key is unknown (or `maintain_key_index` is disabled).
+    pub fn scroll_to_key_offset(&self, key: &K, align: Align) -> Option<u64> {
+        let index = self.index_for_key(key)?;
+        Some(self.scroll_to_index_offset(index, align))
+    }
+
+    /// Same as [`Self::scroll_to_index_offset`], but uses an explicit `viewport_size` instead of
+    /// the stored one.
+    ///
+    /// This is useful to precompute targets before the first layout (e.g. an initial offset from a
+    /// saved index).
+    pub fn scroll_to_index_offset_for(
+        &self,
+        index: usize,
+        align: Align,
+        viewport_size: u32,
+    ) -> u64 {
         if !self.options.enabled {
             return self.options.initial_offset.resolve();
         }
@@ -1004,32 +2494,145 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         }
         let index = index.min(self.options.count - 1);
         let item = self.item(index);
+        self.scroll_to_bounds_offset(item.start, item.end(), align, viewport_size)
+    }
 
-        let sp_start = self.options.scroll_padding_start as u64;
-        let sp_end = self.options.scroll_padding_end as u64;
-        let view = self.viewport_size as u64;
+    /// Computes the (clamped) scroll offset that keeps `index` inside a comfort zone, like an
+    /// editor's cursor "scrolloff".
+    ///
+    /// The item is kept within `[scroll_offset + lead_margin_px, scroll_offset + viewport_size -
+    /// trail_margin_px]`, scrolling only the minimum needed; if it is already inside, the current
+    /// offset is returned unchanged. If the item plus both margins doesn't fit, its start is kept
+    /// `lead_margin_px` below the viewport start. The margins replace `scroll_padding_*` here.
+    pub fn scroll_to_index_offset_with_margins(
+        &self,
+        index: usize,
+        lead_margin_px: u32,
+        trail_margin_px: u32,
+    ) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        if self.options.count == 0 {
+            return 0;
+        }
+        let item = self.item(index.min(self.options.count - 1));
+        align_target(
+            item.start,
+            item.end(),
+            Align::Nearest,
+            self.scroll_offset,
+            self.viewport_size,
+            (lead_margin_px, trail_margin_px),
+            self.options.center_rounding,
+        )
+        .min(self.max_scroll_offset())
+    }
 
-        let target = match align {
-            Align::Start => item.start.saturating_sub(sp_start),
-            Align::End => item.end().saturating_add(sp_end).saturating_sub(view),
-            Align::Center => {
-                let center = item.start.saturating_add(item.size as u64 / 2);
-                center.saturating_sub(view / 2)
-            }
-            Align::Auto => {
-                let cur = self.scroll_offset;
-                let cur_end = cur.saturating_add(view);
-                if item.start >= cur && item.end() <= cur_end {
-                    cur
-                } else if item.start < cur {
-                    item.start.saturating_sub(sp_start)
-                } else {
-                    item.end().saturating_add(sp_end).saturating_sub(view)
-                }
-            }
+    /// Computes the scroll offset that reveals the span from `start` through `end` (inclusive).
+    ///
+    /// If the span fits in the viewport (minus scroll padding), it is aligned as a single region
+    /// per `align` (`Align::Auto` scrolls minimally, `Align::Center` centers the span, ...).
+    /// Otherwise this falls back to aligning item `start` at the viewport start. Indexes are
+    /// clamped to `[0, count)` and swapped if reversed.
+    pub fn scroll_to_range_offset(&self, start: usize, end: usize, align: Align) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        let count = self.options.count;
+        if count == 0 {
+            return 0;
+        }
+        let (start, end) = (start.min(end).min(count - 1), start.max(end).min(count - 1));
+        let span_start = self.item(start).start;
+        let span_end = self.item(end).end();
+        let room = (self.viewport_size as u64).saturating_sub(
+            self.options.scroll_padding_start as u64 + self.options.scroll_padding_end as u64,
+        );
+        if span_end.saturating_sub(span_start) <= room {
+            self.scroll_to_bounds_offset(span_start, span_end, align, self.viewport_size)
+        } else {
+            self.scroll_to_index_offset(start, Align::Start)
+        }
+    }
+
+    /// Computes the scroll offset that aligns a point inside an item rather than the item's box.
+    ///
+    /// The point is `item_start(index) + offset_within` (`offset_within` is clamped to the item
+    /// size). `Align::Start` puts the point at the viewport start, `Align::Center` centers it and
+    /// `Align::End` puts it at the viewport end. This is useful for tall/scrollable items (e.g.
+    /// revealing a matched line inside a large item).
+    pub fn scroll_to_index_offset_within(
+        &self,
+        index: usize,
+        offset_within: u32,
+        align: Align,
+    ) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        if self.options.count == 0 {
+            return 0;
+        }
+        let index = index.min(self.options.count - 1);
+        let item = self.item(index);
+        let point = item
+            .start
+            .saturating_add(offset_within.min(item.size) as u64);
+        self.scroll_to_bounds_offset(point, point, align, self.viewport_size)
+    }
+
+    /// Same as [`Self::scroll_to_index_offset`], but also reveals the gaps surrounding the item.
+    ///
+    /// - `Align::Start` reveals the gap before the item (`leading_gap` for index 0).
+    /// - `Align::End` reveals the gap after the item (`trailing_gap` for the last item).
+    /// - Other alignments treat the item plus both gaps as the target region.
+    ///
+    /// This is useful when gaps render separators that should stay visible.
+    pub fn scroll_to_index_offset_with_gap(&self, index: usize, align: Align) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        let count = self.options.count;
+        if count == 0 {
+            return 0;
+        }
+        let index = index.min(count - 1);
+        let item = self.item(index);
+        let gap_before = if index > 0 {
+            self.gap_after(index - 1)
+        } else {
+            self.options.leading_gap
         };
+        let gap_after = self.gap_following(index);
+        self.scroll_to_bounds_offset(
+            item.start.saturating_sub(gap_before as u64),
+            item.end().saturating_add(gap_after as u64),
+            align,
+            self.viewport_size,
+        )
+    }
 
-        self.clamp_scroll_offset(target)
+    fn scroll_to_bounds_offset(
+        &self,
+        start: u64,
+        end: u64,
+        align: Align,
+        viewport_size: u32,
+    ) -> u64 {
+        let target = align_target(
+            start,
+            end,
+            align,
+            self.scroll_offset,
+            viewport_size,
+            (
+                self.options.scroll_padding_start,
+                self.options.scroll_padding_end,
+            ),
+            self.options.center_rounding,
+        );
+        target.min(self.max_scroll_offset_for(viewport_size))
     }
 
     /// Collects virtual item indexes into `out` (clears `out` first).
@@ -1053,76 +2656,315 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         self.for_each_virtual_index_for(scroll_offset, viewport_size, |i| out.push(i));
     }
 
-    /// Collects virtual items into `out` (clears `out` first).
+    /// Collects virtual items into `out` (clears `out` first).
+    ///
+    /// This is a convenience wrapper around [`Self::for_each_virtual_item`]. For maximum
+    /// performance, prefer `for_each_virtual_item` and reuse a scratch buffer in your adapter.
+    pub fn collect_virtual_items(&self, out: &mut Vec<VirtualItem>) {
+        self.collect_virtual_items_for(self.scroll_offset, self.viewport_size, out);
+    }
+
+    /// Collects virtual items into `out` for a given `scroll_offset`/`viewport_size`.
+    ///
+    /// This clears `out` first.
+    pub fn collect_virtual_items_for(
+        &self,
+        scroll_offset: u64,
+        viewport_size: u32,
+        out: &mut Vec<VirtualItem>,
+    ) {
+        out.clear();
+        self.for_each_virtual_item_for(scroll_offset, viewport_size, |it| out.push(it));
+    }
+
+    /// Collects keyed virtual items into `out` (clears `out` first).
+    ///
+    /// This is a convenience wrapper around [`Self::for_each_virtual_item_keyed`].
+    pub fn collect_virtual_items_keyed(&self, out: &mut Vec<VirtualItemKeyed<K>>) {
+        self.collect_virtual_items_keyed_for(self.scroll_offset, self.viewport_size, out);
+    }
+
+    /// Collects keyed virtual items into `out` for a given `scroll_offset`/`viewport_size`.
+    ///
+    /// This clears `out` first.
+    pub fn collect_virtual_items_keyed_for(
+        &self,
+        scroll_offset: u64,
+        viewport_size: u32,
+        out: &mut Vec<VirtualItemKeyed<K>>,
+    ) {
+        out.clear();
+        self.for_each_virtual_item_keyed_for(scroll_offset, viewport_size, |it| out.push(it));
+    }
+
+    pub fn index_at_offset(&self, offset: u64) -> Option<usize> {
+        if !self.options.enabled {
+            return None;
+        }
+        self.index_at_offset_inner(offset)
+            .filter(|&i| i < self.options.count)
+    }
+
+    /// Classifies a scroll container offset for hit-testing.
+    ///
+    /// Unlike [`Self::index_at_offset`], which clamps to the nearest item, this tells apart
+    /// items, the gaps between them, the paddings and the space outside the list (e.g. to
+    /// deselect on a click below the last item). Returns [`HitRegion::AfterList`] when disabled.
+    pub fn hit_region(&self, offset: u64) -> HitRegion {
+        if !self.options.enabled {
+            return HitRegion::AfterList;
+        }
+        if offset < self.positive_margin() {
+            return HitRegion::BeforeList;
+        }
+        let list = self.container_to_list(offset);
+        let items_start = self.start_inset();
+        if list < items_start {
+            return HitRegion::PaddingStart;
+        }
+        let items_end = items_start.saturating_add(self.sums.total());
+        if list >= items_end {
+            return if list < items_end.saturating_add(self.end_inset()) {
+                HitRegion::PaddingEnd
+            } else {
+                HitRegion::AfterList
+            };
+        }
+        let Some(index) = self.index_at_offset_inner_list(list) else {
+            return HitRegion::AfterList;
+        };
+        let end = self
+            .start_of(index)
+            .saturating_add(self.sizes[index] as u64);
+        if list < end {
+            HitRegion::Item(index)
+        } else {
+            HitRegion::Gap(index)
+        }
+    }
+
+    /// Resolves `offset` to `(item_index, line_within_item)` for items that wrap onto several
+    /// visual lines (e.g. chip rows), for keyboard scrolling by visual line.
+    ///
+    /// Each item's size is split evenly into `lines_per_item(index)` lines (at least 1). Offsets
+    /// use the same space as [`Self::index_at_offset`]; offsets before the item's start or in the
+    /// gap after it clamp to its first/last line. Returns `None` when disabled or empty.
+    pub fn line_at_offset(
+        &self,
+        offset: u64,
+        lines_per_item: impl Fn(usize) -> u32,
+    ) -> Option<(usize, u32)> {
+        let index = self.index_at_offset(offset)?;
+        let item = self.item(index);
+        let lines = lines_per_item(index).max(1);
+        if item.size == 0 {
+            return Some((index, 0));
+        }
+        let within = offset.saturating_sub(item.start) as u128;
+        let line = (within * lines as u128 / item.size as u128).min(lines as u128 - 1) as u32;
+        Some((index, line))
+    }
+
+    /// Returns how many items end at or before `offset` (i.e. lie fully above it).
+    ///
+    /// Offsets use the same space as [`Self::item_start`] (including `scroll_margin`). Unlike
+    /// [`Self::index_at_offset`], which returns the item *at* `offset`, this counts items that
+    /// were scrolled past:
+    /// - An offset exactly at item `i`'s start returns `i`.
+    /// - An offset inside item `i` returns `i`.
+    /// - An offset at item `i`'s end, or in the gap after it, returns `i + 1`.
+    ///
+    /// Returns 0 when disabled.
+    pub fn items_before_offset(&self, offset: u64) -> usize {
+        if !self.options.enabled {
+            return 0;
+        }
+        let count = self.options.count;
+        if offset < self.positive_margin() {
+            return 0;
+        }
+        let offset = self.container_to_list(offset);
+        if offset < self.start_inset() || count == 0 {
+            return 0;
+        }
+        let off = offset - self.start_inset();
+        let consumed = self.sums.lower_bound(off).min(count);
+        if consumed < count {
+            let end = self
+                .sums
+                .prefix_sum(consumed)
+                .saturating_add(self.sizes[consumed] as u64);
+            if end <= off {
+                return consumed + 1;
+            }
+        }
+        consumed
+    }
+
+    pub fn item_start(&self, index: usize) -> Option<u64> {
+        if !self.options.enabled {
+            return None;
+        }
+        (index < self.options.count).then(|| self.list_to_container(self.start_of(index)))
+    }
+
+    pub fn item_size(&self, index: usize) -> Option<u32> {
+        if !self.options.enabled {
+            return None;
+        }
+        self.sizes.get(index).copied()
+    }
+
+    /// Returns the item size plus the gap rendered after it (`trailing_gap` for the last item).
     ///
-    /// This is a convenience wrapper around [`Self::for_each_virtual_item`]. For maximum
-    /// performance, prefer `for_each_virtual_item` and reuse a scratch buffer in your adapter.
-    pub fn collect_virtual_items(&self, out: &mut Vec<VirtualItem>) {
-        self.collect_virtual_items_for(self.scroll_offset, self.viewport_size, out);
+    /// Saturates at `u32::MAX` for items near the maximum size.
+    pub fn item_size_with_gap(&self, index: usize) -> Option<u32> {
+        let size = self.item_size(index)?;
+        Some(size.saturating_add(self.gap_following(index)))
     }
 
-    /// Collects virtual items into `out` for a given `scroll_offset`/`viewport_size`.
-    ///
-    /// This clears `out` first.
-    pub fn collect_virtual_items_for(
-        &self,
-        scroll_offset: u64,
-        viewport_size: u32,
-        out: &mut Vec<VirtualItem>,
-    ) {
-        out.clear();
-        self.for_each_virtual_item_for(scroll_offset, viewport_size, |it| out.push(it));
+    pub fn item_end(&self, index: usize) -> Option<u64> {
+        self.item_bounds(index).map(|(_, end)| end)
     }
 
-    /// Collects keyed virtual items into `out` (clears `out` first).
+    /// Returns `(start, end)` for `index` with a single prefix-sum query.
     ///
-    /// This is a convenience wrapper around [`Self::for_each_virtual_item_keyed`].
-    pub fn collect_virtual_items_keyed(&self, out: &mut Vec<VirtualItemKeyed<K>>) {
-        self.collect_virtual_items_keyed_for(self.scroll_offset, self.viewport_size, out);
+    /// Like [`Self::item_start`], offsets include `scroll_margin` and `padding_start`. Returns
+    /// `None` when disabled or out of bounds.
+    pub fn item_bounds(&self, index: usize) -> Option<(u64, u64)> {
+        let item = self.virtual_item(index)?;
+        Some((item.start, item.end()))
     }
 
-    /// Collects keyed virtual items into `out` for a given `scroll_offset`/`viewport_size`.
+    /// Returns the [`VirtualItem`] for `index` (same positioning as `for_each_virtual_item`).
     ///
-    /// This clears `out` first.
-    pub fn collect_virtual_items_keyed_for(
-        &self,
-        scroll_offset: u64,
-        viewport_size: u32,
-        out: &mut Vec<VirtualItemKeyed<K>>,
-    ) {
-        out.clear();
-        self.for_each_virtual_item_keyed_for(scroll_offset, viewport_size, |it| out.push(it));
+    /// Returns `None` when disabled or out of bounds.
+    pub fn virtual_item(&self, index: usize) -> Option<VirtualItem> {
+        if !self.options.enabled || index >= self.options.count {
+            return None;
+        }
+        Some(self.item(index))
     }
 
-    pub fn index_at_offset(&self, offset: u64) -> Option<usize> {
-        if !self.options.enabled {
+    /// Describes the rendered window as a regular grid when every item has the same size.
+    ///
+    /// Returns `Some` only when the layout is provably uniform: all current sizes (estimated or
+    /// measured) are equal, no `gap_after` or `range_extractor` is set, and the virtualizer is
+    /// enabled and non-empty. Item `i` in the window then starts at
+    /// `first_start + (i - start_index) * (item_size + gap)`.
+    ///
+    /// The uniformity check runs in `O(n)` the first time after a size change (stopping at the
+    /// first differing size) and is cached until the next one.
+    pub fn uniform_window(&self) -> Option<UniformWindow> {
+        if !self.options.enabled
+            || self.options.count == 0
+            || self.options.gap_after.is_some()
+            || self.options.range_extractor.is_some()
+        {
             return None;
         }
-        self.index_at_offset_inner(offset)
-            .filter(|&i| i < self.options.count)
+        let item_size = match self.uniform_size.get() {
+            Some(cached) => cached,
+            None => {
+                let first = self.sizes[0];
+                let uniform = self.sizes.iter().all(|&s| s == first).then_some(first);
+                self.uniform_size.set(Some(uniform));
+                uniform
+            }
+        }?;
+        let range = self.virtual_range();
+        let first_start = if range.start_index < self.options.count {
+            self.item(range.start_index).start
+        } else {
+            self.list_to_container(self.start_of(range.start_index))
+        };
+        Some(UniformWindow {
+            start_index: range.start_index,
+            end_index: range.end_index,
+            item_size,
+            first_start,
+            gap: self.options.gap,
+        })
     }
 
-    pub fn item_start(&self, index: usize) -> Option<u64> {
+    /// Returns `(index, start, size)` for every item (debug-only helper).
+    ///
+    /// This runs in `O(n)` and allocates; it is intended for snapshot tests and bug reports, not
+    /// for per-frame rendering. Like [`VirtualItem::start`], starts include `scroll_margin` and
+    /// `padding_start`. Returns an empty `Vec` when disabled.
+    pub fn debug_positions(&self) -> Vec<(usize, u64, u32)> {
         if !self.options.enabled {
-            return None;
+            return Vec::new();
+        }
+        let count = self.options.count;
+        let mut out = Vec::with_capacity(count);
+        let mut start = self.start_of(0);
+        for i in 0..count {
+            let size = self.sizes[i];
+            out.push((i, self.list_to_container(start), size));
+            start = start
+                .saturating_add(size as u64)
+                .saturating_add(self.gap_after(i) as u64);
         }
-        (index < self.options.count).then(|| {
-            let margin = self.options.scroll_margin as u64;
-            margin.saturating_add(self.start_of(index))
+        out
+    }
+
+    /// Returns the active sticky item: the last rendered index for which `is_sticky` returns
+    /// `true` whose natural start is at or above the current scroll offset.
+    ///
+    /// The returned item's `start` is where a sticky overlay is rendered (the scroll offset, i.e.
+    /// the top of the viewport), not its natural layout position. Only indexes emitted by the
+    /// `range_extractor` (or the overscanned range without one) are considered.
+    pub fn active_sticky_item(&self, is_sticky: impl Fn(usize) -> bool) -> Option<VirtualItem> {
+        let mut active = None;
+        self.for_each_virtual_index(|i| {
+            if is_sticky(i) && self.item(i).start <= self.scroll_offset {
+                active = Some(i);
+            }
+        });
+        active.map(|index| VirtualItem {
+            index,
+            start: self.scroll_offset,
+            size: self.sizes[index],
         })
     }
 
-    pub fn item_size(&self, index: usize) -> Option<u32> {
+    /// Returns the footer to pin to the bottom of the viewport: the smallest index in
+    /// `footer_indices` whose natural end lies below the viewport (`item_end > scroll_offset +
+    /// viewport_size`), i.e. a footer the user has not scrolled to yet.
+    ///
+    /// This is the bottom-edge counterpart of [`Self::active_sticky_item`]. Indexes may be given
+    /// in any order; out-of-bounds indexes are ignored. Returns `None` when every footer is
+    /// reached (render them inline) or the virtualizer is disabled.
+    pub fn sticky_footer_index(&self, footer_indices: &[usize]) -> Option<usize> {
         if !self.options.enabled {
             return None;
         }
-        self.sizes.get(index).copied()
+        let view_end = self.scroll_offset.saturating_add(self.viewport_size as u64);
+        footer_indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.options.count && self.item(i).end() > view_end)
+            .min()
     }
 
-    pub fn item_end(&self, index: usize) -> Option<u64> {
-        let start = self.item_start(index)?;
-        let size = self.item_size(index)? as u64;
-        Some(start.saturating_add(size))
+    /// Hit-tests an offset, taking a sticky overlay into account.
+    ///
+    /// [`Self::index_at_offset`] (and `virtual_item_for_offset`) only consider the natural
+    /// layout, so they return the scrolled item underneath a pinned header. This returns the
+    /// [`Self::active_sticky_item`] if `offset` falls inside its overlay, and the item at
+    /// `offset` otherwise.
+    ///
+    /// "Push-up" effects (the next sticky item pushing the active one out) are not modeled; handle
+    /// those in the adapter using the layout from `for_each_virtual_item`.
+    pub fn hit_test(&self, offset: u64, is_sticky: impl Fn(usize) -> bool) -> Option<usize> {
+        if let Some(sticky) = self.active_sticky_item(is_sticky)
+            && offset >= sticky.start
+            && offset < sticky.end()
+        {
+            return Some(sticky.index);
+        }
+        self.index_at_offset(offset)
     }
 
     pub fn virtual_item_for_offset(&self, offset: u64) -> Option<VirtualItem> {
@@ -1141,53 +2983,162 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         })
     }
 
+    /// Returns the key of the item at `offset` (resolved via [`Self::index_at_offset`]) without
+    /// computing its geometry, e.g. for drag-and-drop hit-testing.
+    ///
+    /// Returns `None` when disabled or empty.
+    pub fn key_at_offset(&self, offset: u64) -> Option<K> {
+        let index = self.index_at_offset(offset)?;
+        Some(self.key_for(index))
+    }
+
     fn rebuild_estimates(&mut self) {
+        self.rebuild_estimates_quiet();
+        self.notify_structural(StructuralChange::Estimates);
+    }
+
+    /// Same as `rebuild_estimates`, without firing `on_structural_change`.
+    fn rebuild_estimates_quiet(&mut self) {
         vdebug!(
             count = self.options.count,
             cached = self.key_sizes.len(),
             "rebuild_estimates"
         );
+        self.reload_sizes();
+        self.rebuild_key_index();
+        self.truncate_cross_sizes(self.options.count);
+    }
+
+    /// Rebuilds `sizes`/`measured` and the prefix sums.
+    fn reload_sizes(&mut self) {
+        match self.load_sizes() {
+            Some(size) if self.options.gap_after.is_none() => {
+                self.install_sums(Fenwick::from_uniform(
+                    self.sizes.len(),
+                    size,
+                    self.options.gap,
+                ));
+                self.uniform_size.set(Some(Some(size)));
+            }
+            _ => self.rebuild_fenwick(),
+        }
+    }
+
+    /// Rebuilds `sizes`/`measured` from the key-based cache, estimating the rest.
+    ///
+    /// Returns `Some(size)` when every item got the same `uniform_estimate` without any per-item
+    /// lookups (no cached measurements, context-aware estimate or pins).
+    fn load_sizes(&mut self) -> Option<u32> {
+        let count = self.options.count;
         self.sizes.clear();
         self.measured.clear();
-        self.sizes
-            .reserve_exact(self.options.count.saturating_sub(self.sizes.len()));
-        self.measured
-            .reserve_exact(self.options.count.saturating_sub(self.measured.len()));
+        self.sizes.reserve_exact(count);
+        self.measured.reserve_exact(count);
 
-        for i in 0..self.options.count {
+        if let Some(size) = self.options.uniform_estimate
+            && self.options.estimate_size_ctx.is_none()
+            && self.options.always_pinned.is_empty()
+            && self.key_sizes.is_empty()
+        {
+            let size = self.clamp_size(size);
+            self.sizes.resize(count, size);
+            self.measured.resize(count, false);
+            return Some(size);
+        }
+
+        // Cached sizes first, so context-aware estimates can see measured neighbours on both
+        // sides.
+        for i in 0..count {
             let key = self.key_for(i);
-            if let Some(&measured_size) = self.key_sizes.get(&key) {
-                self.sizes.push(measured_size);
-                self.measured.push(true);
-            } else {
-                self.sizes.push((self.options.estimate_size)(i));
-                self.measured.push(false);
+            match self.key_sizes.get(&key) {
+                Some(&measured_size) => {
+                    self.sizes.push(self.clamp_size(measured_size));
+                    self.measured.push(true);
+                }
+                None => {
+                    self.sizes.push(0);
+                    self.measured.push(false);
+                }
             }
         }
-        self.rebuild_fenwick();
+        for i in 0..count {
+            if !self.measured[i] {
+                self.sizes[i] = self.estimate_for(i);
+            }
+        }
+        self.zero_pinned_sizes();
+        None
+    }
+
+    /// `always_pinned` items take no space in the layout; their overlay size is looked up on
+    /// demand (see `pinned_size`).
+    fn zero_pinned_sizes(&mut self) {
+        for &i in &self.options.always_pinned {
+            match self.sizes.get_mut(i) {
+                Some(size) => *size = 0,
+                None => break,
+            }
+        }
+    }
+
+    fn rebuild_key_index(&mut self) {
+        if !self.options.maintain_key_index {
+            // Release the memory rather than keeping an empty, pre-sized map around.
+            self.key_index = KeyIndexMap::<K>::new();
+            return;
+        }
+        self.key_index.clear();
+        for i in 0..self.options.count {
+            let key = self.key_for(i);
+            self.key_index.insert(key, i);
+        }
     }
 
     fn rebuild_fenwick(&mut self) {
-        self.sums = Fenwick::from_sizes(&self.sizes, self.options.gap);
+        self.uniform_size.set(None);
+        let sums = if !self.options.always_pinned.is_empty() {
+            Fenwick::from_sizes_with_gaps(&self.sizes, |i| self.gap_value(i))
+        } else {
+            match &self.options.gap_after {
+                Some(gap_after) => Fenwick::from_sizes_with_gaps(&self.sizes, |i| gap_after(i)),
+                None => Fenwick::from_sizes(&self.sizes, self.options.gap),
+            }
+        };
+        self.install_sums(sums);
+    }
+
+    fn install_sums(&mut self, sums: Fenwick) {
+        let capacity = self.sums.capacity();
+        self.sums = sums;
+        // Keep capacity reserved via `with_capacity`/`reserve` across rebuilds.
+        self.sums
+            .reserve_exact(capacity.saturating_sub(self.sums.len()));
     }
 
     fn resize_count(&mut self, prev_count: usize, new_count: usize) {
+        self.resize_count_quiet(prev_count, new_count);
+        self.notify_structural(StructuralChange::Count);
+    }
+
+    fn resize_count_quiet(&mut self, prev_count: usize, new_count: usize) {
+        self.uniform_size.set(None);
         if self.sizes.len() != prev_count
             || self.measured.len() != prev_count
             || self.sums.len() != prev_count
         {
             // Defensive fallback: if internal invariants don't match the expected previous count,
             // rebuild from scratch (preserves correctness at the expense of performance).
-            self.rebuild_estimates();
+            self.rebuild_estimates_quiet();
             return;
         }
 
-        let gap = self.options.gap as u64;
-
         if new_count > prev_count {
-            if gap > 0 && prev_count > 0 {
+            if prev_count > 0 {
                 // The previous last item was stored without a trailing gap. It is no longer last.
-                self.sums.add(prev_count - 1, gap as i64);
+                let gap = self.gap_value(prev_count - 1);
+                if gap > 0 {
+                    self.sums.add(prev_count - 1, gap as i64);
+                }
             }
 
             self.sizes.reserve_exact(new_count - prev_count);
@@ -1196,72 +3147,338 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             for i in prev_count..new_count {
                 let key = self.key_for(i);
                 let (size, is_measured) = if let Some(&measured_size) = self.key_sizes.get(&key) {
-                    (measured_size, true)
+                    (self.clamp_size(measured_size), true)
                 } else {
-                    ((self.options.estimate_size)(i), false)
+                    (self.estimate_for(i), false)
                 };
 
+                let size = if self.is_always_pinned(i) { 0 } else { size };
                 self.sizes.push(size);
                 self.measured.push(is_measured);
 
-                let mut value = size as u64;
-                if gap > 0 && i + 1 < new_count {
-                    value = value.saturating_add(gap);
-                }
+                let value = (size as u64).saturating_add(self.gap_after(i) as u64);
                 self.sums.push_value(value);
+
+                if self.options.maintain_key_index {
+                    self.key_index.insert(key, i);
+                }
             }
             return;
         }
 
         // Shrink (or clear).
+        self.truncate_cross_sizes(new_count);
         self.sizes.truncate(new_count);
         self.measured.truncate(new_count);
         self.sums.truncate(new_count);
 
-        if gap > 0 && new_count > 0 && new_count < prev_count {
+        if new_count > 0 && new_count < prev_count {
             // The new last item previously had a trailing gap; remove it.
-            self.sums.add(new_count - 1, -(gap as i64));
+            let gap = self.gap_value(new_count - 1);
+            if gap > 0 {
+                self.sums.add(new_count - 1, -(gap as i64));
+            }
         }
+        self.rebuild_key_index();
     }
 
     fn item(&self, index: usize) -> VirtualItem {
-        let margin = self.options.scroll_margin as u64;
-        let start = margin.saturating_add(self.start_of(index));
         VirtualItem {
             index,
-            start,
+            start: self.list_to_container(self.start_of(index)),
             size: self.sizes[index],
         }
     }
 
+    /// Maps a list-space offset to the scroll container (adds the signed `scroll_margin`,
+    /// saturating at 0).
+    fn list_to_container(&self, offset: u64) -> u64 {
+        let margin = self.options.scroll_margin;
+        if margin >= 0 {
+            offset.saturating_add(margin as u64)
+        } else {
+            offset.saturating_sub(margin.unsigned_abs())
+        }
+    }
+
+    /// Maps a scroll container offset to list space (subtracts the signed `scroll_margin`,
+    /// saturating at 0).
+    fn container_to_list(&self, offset: u64) -> u64 {
+        let margin = self.options.scroll_margin;
+        if margin >= 0 {
+            offset.saturating_sub(margin as u64)
+        } else {
+            offset.saturating_add(margin.unsigned_abs())
+        }
+    }
+
+    /// Returns the container offset where the list starts if it is past the origin.
+    fn positive_margin(&self) -> u64 {
+        self.options.scroll_margin.max(0) as u64
+    }
+
+    /// Space before the first item: `padding_start + leading_gap`.
+    fn start_inset(&self) -> u64 {
+        self.options.padding_start as u64 + self.options.leading_gap as u64
+    }
+
+    /// Space after the last item: `trailing_gap + padding_end`.
+    fn end_inset(&self) -> u64 {
+        self.options.trailing_gap as u64 + self.options.padding_end as u64
+    }
+
+    /// Returns the gap placed after `index` (no gap after the last item).
+    fn gap_after(&self, index: usize) -> u32 {
+        if index + 1 < self.options.count {
+            self.gap_value(index)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the gap rendered after `index`, using `trailing_gap` for the last item.
+    fn gap_following(&self, index: usize) -> u32 {
+        if index + 1 < self.options.count {
+            self.gap_value(index)
+        } else {
+            self.options.trailing_gap
+        }
+    }
+
+    /// Returns the configured gap after `index`, ignoring whether it is the last item.
+    fn gap_value(&self, index: usize) -> u32 {
+        if self.is_always_pinned(index) {
+            return 0;
+        }
+        match &self.options.gap_after {
+            Some(f) => f(index),
+            None => self.options.gap,
+        }
+    }
+
     fn start_of(&self, index: usize) -> u64 {
-        self.options.padding_start as u64 + self.sums.prefix_sum(index)
+        self.start_inset()
+            .saturating_add(self.sums.prefix_sum(index))
     }
 
     pub fn max_scroll_offset(&self) -> u64 {
+        self.max_scroll_offset_for(self.viewport_size)
+    }
+
+    /// Returns [`Self::max_scroll_offset`] in list coordinates (`scroll_margin` removed, saturating
+    /// at 0), for adapters that scroll list-local content. Returns 0 when disabled.
+    pub fn max_scroll_offset_in_list(&self) -> u64 {
         if !self.options.enabled {
-            return self.options.initial_offset.resolve();
+            return 0;
+        }
+        self.container_to_list(self.max_scroll_offset())
+    }
+
+    /// Returns the scroll position within the list as a fraction in `[0, 1]` (for scrollbars).
+    ///
+    /// Computed in list space (`scroll_margin` removed) as `offset / max_offset`. Returns `0.0`
+    /// when the content fits in the viewport or the virtualizer is disabled.
+    pub fn scroll_progress(&self) -> f32 {
+        if !self.options.enabled || self.is_fully_visible() {
+            return 0.0;
+        }
+        let max = self.container_to_list(self.max_scroll_offset());
+        if max == 0 {
+            return 0.0;
+        }
+        let offset = self.container_to_list(self.scroll_offset).min(max);
+        (offset as f64 / max as f64) as f32
+    }
+
+    /// Returns `viewport_size / total_size` clamped to `[0, 1]` (the scrollbar thumb length as a
+    /// fraction of the track).
+    ///
+    /// Returns `1.0` when the content fits in the viewport or the virtualizer is disabled.
+    pub fn thumb_size_fraction(&self) -> f32 {
+        if !self.options.enabled || self.is_fully_visible() {
+            return 1.0;
         }
-        let margin = self.options.scroll_margin as u64;
         let total = self.total_size();
-        let view = self.viewport_size as u64;
-        margin.saturating_add(total.saturating_sub(view))
+        if total == 0 {
+            return 1.0;
+        }
+        (self.viewport_size as f64 / total as f64).min(1.0) as f32
+    }
+
+    fn max_scroll_offset_for(&self, viewport_size: u32) -> u64 {
+        if !self.options.enabled {
+            return self.options.initial_offset.resolve();
+        }
+        let view = viewport_size as u64;
+        self.list_to_container(self.total_size())
+            .saturating_sub(view)
+    }
+
+    /// Returns `true` if all content (including `scroll_margin` and paddings) fits in the
+    /// viewport, i.e. every item is visible and no scrolling is possible.
+    ///
+    /// Adapters can use this to skip scrollbars/overscan for small lists. Returns `true` when
+    /// disabled.
+    pub fn is_fully_visible(&self) -> bool {
+        if !self.options.enabled {
+            return true;
+        }
+        self.list_to_container(self.total_size()) <= self.viewport_size as u64
+    }
+
+    /// Inverse of [`Self::is_fully_visible`].
+    pub fn can_scroll(&self) -> bool {
+        !self.is_fully_visible()
     }
 
     pub fn clamp_scroll_offset(&self, offset: u64) -> u64 {
         offset.min(self.max_scroll_offset())
     }
 
+    /// Returns how far `requested` lies outside `[0, max_scroll_offset]`.
+    ///
+    /// - Positive: past the end (`requested - max_scroll_offset`).
+    /// - Negative: before the start (`requested`, for underscroll below 0).
+    /// - Zero: within bounds.
+    ///
+    /// This is useful to drive rubber-band/overscroll affordances.
+    pub fn overshoot(&self, requested: i64) -> i64 {
+        if requested < 0 {
+            return requested;
+        }
+        let max = self.max_scroll_offset().min(i64::MAX as u64) as i64;
+        requested.saturating_sub(max).max(0)
+    }
+
+    /// Returns the overshoot recorded by the most recent `apply_scroll_offset_event*` /
+    /// `apply_scroll_frame*` call (see [`Self::overshoot`]).
+    ///
+    /// This is reset to 0 when scrolling stops.
+    pub fn last_overshoot(&self) -> i64 {
+        self.last_overshoot
+    }
+
+    fn record_overshoot(&mut self, requested: u64) {
+        self.last_overshoot = self.overshoot(requested.min(i64::MAX as u64) as i64);
+    }
+
+    /// Returns the scroll velocity in px/ms (negative when scrolling backward).
+    ///
+    /// Computed from the last two `apply_scroll_offset_event*` / `apply_scroll_frame*` calls
+    /// (applied offsets and their timestamps). Returns `0.0` until two samples with distinct
+    /// timestamps are available, and resets to `0.0` when scrolling stops.
+    pub fn scroll_velocity(&self) -> f32 {
+        self.scroll_velocity
+    }
+
+    fn record_scroll_sample(&mut self, now_ms: u64) {
+        let offset = self.scroll_offset;
+        if let Some((prev_offset, prev_ms)) = self.last_scroll_sample
+            && now_ms > prev_ms
+        {
+            let delta = offset as f64 - prev_offset as f64;
+            self.scroll_velocity = (delta / (now_ms - prev_ms) as f64) as f32;
+        }
+        self.last_scroll_sample = Some((offset, now_ms));
+    }
+
+    /// Returns the size the UI scroll container should use.
+    ///
+    /// This equals [`Self::total_size`] unless `max_total_size` is set and exceeded, in which case
+    /// the cap is returned.
+    pub fn rendered_total_size(&self) -> u64 {
+        let total = self.total_size();
+        match self.options.max_total_size {
+            Some(max) => total.min(max),
+            None => total,
+        }
+    }
+
+    /// Returns `true` if offsets are compressed because `total_size` exceeds `max_total_size`.
+    pub fn is_scaled(&self) -> bool {
+        self.rendered_total_size() < self.total_size()
+    }
+
+    /// Converts a scroll offset from virtual space into rendered (compressed) space.
+    ///
+    /// The scrollable ranges `[0, max_scroll_offset]` are mapped proportionally onto each other,
+    /// so both ends of the list stay reachable. When the list is not scaled, this is the identity.
+    ///
+    /// Precision: one rendered unit covers roughly `total_size / max_total_size` virtual units,
+    /// so `to_virtual(to_rendered(x))` may differ from `x` by up to that amount. Items positioned
+    /// relative to the current offset (`item.start - scroll_offset`) are still pixel-exact.
+    pub fn to_rendered(&self, offset: u64) -> u64 {
+        let Some((virtual_max, rendered_max)) = self.scaled_scroll_extents() else {
+            return offset;
+        };
+        let offset = offset.min(virtual_max);
+        (offset as u128 * rendered_max as u128 / virtual_max as u128) as u64
+    }
+
+    /// Converts a scroll offset from rendered (compressed) space into virtual space.
+    ///
+    /// This is the inverse of [`Self::to_rendered`]; see its docs for precision limits. Feed UI
+    /// scroll events through this before calling `apply_scroll_offset_event*`.
+    pub fn to_virtual(&self, offset: u64) -> u64 {
+        let Some((virtual_max, rendered_max)) = self.scaled_scroll_extents() else {
+            return offset;
+        };
+        let offset = offset.min(rendered_max);
+        (offset as u128 * virtual_max as u128 / rendered_max as u128) as u64
+    }
+
+    fn scaled_scroll_extents(&self) -> Option<(u64, u64)> {
+        if !self.options.enabled || !self.is_scaled() {
+            return None;
+        }
+        let view = self.viewport_size as u64;
+        let virtual_max = self.max_scroll_offset();
+        let rendered_max = self
+            .list_to_container(self.rendered_total_size())
+            .saturating_sub(view);
+        (virtual_max > 0 && rendered_max > 0).then_some((virtual_max, rendered_max))
+    }
+
     fn compute_range(&self, scroll_offset: u64, viewport_size: u32) -> VirtualRange {
-        let mut range = self.compute_visible_range(scroll_offset, viewport_size);
+        let range = self.compute_visible_range(scroll_offset, viewport_size);
         if range.is_empty() {
             return range;
         }
+        self.overscanned(range)
+    }
 
-        let count = self.options.count;
-        let overscan = self.options.overscan;
-        range.start_index = range.start_index.saturating_sub(overscan);
-        range.end_index = cmp::min(count, range.end_index.saturating_add(overscan));
+    /// Expands a non-empty visible range by `overscan_px` (when set) or `overscan` items.
+    fn overscanned(&self, visible: VirtualRange) -> VirtualRange {
+        let Some(px) = self.options.overscan_px else {
+            return self.expand_range(visible, self.options.overscan);
+        };
+        if px == 0 {
+            return visible;
+        }
+        let px = px as u64;
+        let last = visible.end_index - 1;
+        let band_start = self.start_of(visible.start_index).saturating_sub(px);
+        let band_end = self
+            .start_of(last)
+            .saturating_add(self.sizes[last] as u64)
+            .saturating_add(px);
+        let start_index = self
+            .index_at_offset_inner_list(band_start)
+            .unwrap_or(0)
+            .min(visible.start_index);
+        let end_index = self
+            .index_at_offset_inner_list(band_end - 1)
+            .map_or(visible.end_index, |i| i + 1)
+            .clamp(visible.end_index, self.options.count);
+        VirtualRange {
+            start_index,
+            end_index,
+        }
+    }
+
+    fn expand_range(&self, mut range: VirtualRange, by: usize) -> VirtualRange {
+        range.start_index = range.start_index.saturating_sub(by);
+        range.end_index = cmp::min(self.options.count, range.end_index.saturating_add(by));
         range
     }
 
@@ -1274,22 +3491,21 @@ impl<K: KeyCacheKey> Virtualizer<K> {
             };
         }
 
-        let margin = self.options.scroll_margin as u64;
         let view = viewport_size as u64;
 
         let total = self.total_size();
-        let max_scroll = margin.saturating_add(total.saturating_sub(view));
+        let max_scroll = self.list_to_container(total).saturating_sub(view);
         let scroll_offset = scroll_offset.min(max_scroll);
         let scroll_end = scroll_offset.saturating_add(view);
-        if scroll_end <= margin {
+        if scroll_end <= self.positive_margin() {
             return VirtualRange {
                 start_index: 0,
                 end_index: 0,
             };
         }
 
-        let off = scroll_offset.saturating_sub(margin);
-        let visible_end_exclusive = scroll_end.saturating_sub(margin);
+        let off = self.container_to_list(scroll_offset);
+        let visible_end_exclusive = self.container_to_list(scroll_end);
 
         if off >= total {
             return VirtualRange {
@@ -1319,15 +3535,14 @@ impl<K: KeyCacheKey> Virtualizer<K> {
     }
 
     fn index_at_offset_inner(&self, offset: u64) -> Option<usize> {
-        let margin = self.options.scroll_margin as u64;
-        if offset < margin {
+        if offset < self.positive_margin() {
             return Some(0);
         }
-        self.index_at_offset_inner_list(offset - margin)
+        self.index_at_offset_inner_list(self.container_to_list(offset))
     }
 
     fn index_at_offset_inner_list(&self, offset: u64) -> Option<usize> {
-        let ps = self.options.padding_start as u64;
+        let ps = self.start_inset();
         if offset < ps {
             return Some(0);
         }
@@ -1344,3 +3559,107 @@ impl<K: KeyCacheKey> Virtualizer<K> {
         Some(consumed.min(count.saturating_sub(1)))
     }
 }
+
+impl Virtualizer<ItemKey> {
+    /// Restores the default index-as-key mapping (`key_for(i) == i as u64`, absolute index) and
+    /// rebuilds sizes from the measurement cache.
+    ///
+    /// Useful when toggling between a reordered view (custom `get_item_key`) and the original
+    /// order.
+    pub fn reset_get_item_key(&mut self) {
+        self.set_get_item_key(|i| i as u64);
+    }
+}
+
+fn clamp_item_size(size: u32, min: u32, max: Option<u32>) -> u32 {
+    let size = size.max(min);
+    match max {
+        Some(max) => size.min(max),
+        None => size,
+    }
+}
+
+/// Computes the (unclamped) scroll offset that aligns `[start, end)` in a viewport.
+pub(crate) fn align_target(
+    start: u64,
+    end: u64,
+    align: Align,
+    scroll_offset: u64,
+    viewport_size: u32,
+    (scroll_padding_start, scroll_padding_end): (u32, u32),
+    rounding: Rounding,
+) -> u64 {
+    let sp_start = scroll_padding_start as u64;
+    let sp_end = scroll_padding_end as u64;
+    let view = viewport_size as u64;
+
+    match align {
+        Align::Start => start.saturating_sub(sp_start),
+        Align::End => end.saturating_add(sp_end).saturating_sub(view),
+        Align::Center => center_target(start, end, view, rounding),
+        Align::Auto | Align::CenterIfNeeded => {
+            let cur = scroll_offset;
+            let cur_end = cur.saturating_add(view);
+            if start >= cur && end <= cur_end {
+                cur
+            } else if align == Align::CenterIfNeeded {
+                center_target(start, end, view, rounding)
+            } else if start < cur {
+                start.saturating_sub(sp_start)
+            } else {
+                end.saturating_add(sp_end).saturating_sub(view)
+            }
+        }
+        Align::Nearest => {
+            let cur = scroll_offset;
+            // Offsets in `[lo, hi]` show the item fully (honouring scroll padding).
+            let lo = end.saturating_add(sp_end).saturating_sub(view);
+            let hi = start.saturating_sub(sp_start);
+            if lo <= hi {
+                cur.clamp(lo, hi)
+            } else if start <= cur && cur.saturating_add(view) <= end {
+                // Over-tall item that already fills the viewport.
+                cur
+            } else {
+                hi
+            }
+        }
+        Align::Fraction(fraction) => {
+            let lo = start.saturating_sub(sp_start);
+            let hi = end.saturating_add(sp_end).saturating_sub(view);
+            if fraction.is_nan() || fraction <= 0.0 {
+                lo
+            } else if fraction >= 1.0 {
+                hi
+            } else {
+                round_offset(lo as f64 + (hi as f64 - lo as f64) * fraction as f64)
+            }
+        }
+    }
+}
+
+/// Rounds a floating-point offset half-to-even. Negative and NaN offsets map to 0, and values
+/// beyond `u64::MAX` saturate.
+fn round_offset(offset: f64) -> u64 {
+    if offset.is_nan() || offset <= 0.0 {
+        return 0;
+    }
+    // `as` truncates (= floor for positive values) and saturates; `core` has no `f64::round`.
+    let floor = offset as u64;
+    let frac = offset - floor as f64;
+    if frac > 0.5 || (frac == 0.5 && floor % 2 == 1) {
+        floor.saturating_add(1)
+    } else {
+        floor
+    }
+}
+
+/// Returns the offset that centers `[start, end)` in a viewport of `view`: `(start + end - view)
+/// / 2`, with half-pixel results rounded per `rounding` (saturating at 0).
+fn center_target(start: u64, end: u64, view: u64, rounding: Rounding) -> u64 {
+    let twice = start.saturating_add(end).saturating_sub(view);
+    match rounding {
+        Rounding::HalfUp => twice / 2 + twice % 2,
+        Rounding::HalfDown => twice / 2,
+    }
+}